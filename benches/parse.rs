@@ -0,0 +1,51 @@
+//! Benchmarks of parsing a single media type.
+//!
+//! Run with `cargo bench --bench parse`. Median times on a single-core x86_64 VM
+//! before and after scanning names and values by bytes with a lookup table.
+//! The "before" column is the best of two runs since the VM is noisy;
+//! compare with `--save-baseline` on the same machine.
+//!
+//! | benchmark         | before   | after    |
+//! |-------------------|----------|----------|
+//! | `parse/essence`   | 72.1 ns  | 60.5 ns  |
+//! | `parse/suffix`    | 70.7 ns  | 57.7 ns  |
+//! | `parse/params`    | 229.9 ns | 204.4 ns |
+//! | `parse/quoted`    | 237.8 ns | 194.1 ns |
+//! | `from_str/params` | 306.0 ns | 252.1 ns |
+//! | `from_str/quoted` | 330.3 ns | 239.7 ns |
+
+use criterion::{black_box, criterion_group, criterion_main, Criterion, Throughput};
+use mediatype::{MediaType, MediaTypeBuf};
+
+const INPUTS: [(&str, &str); 4] = [
+    ("essence", "application/json"),
+    ("suffix", "image/svg+xml"),
+    ("params", "text/html; charset=UTF-8; format=flowed; q=0.9"),
+    (
+        "quoted",
+        "multipart/form-data; boundary=\"----WebKitFormBoundary7MA4YWxkTrZu0gW\"",
+    ),
+];
+
+fn parse(c: &mut Criterion) {
+    let mut group = c.benchmark_group("parse");
+    for (name, input) in INPUTS {
+        group.throughput(Throughput::Bytes(input.len() as u64));
+        group.bench_function(name, |b| b.iter(|| MediaType::parse(black_box(input))));
+    }
+    group.finish();
+}
+
+fn from_str(c: &mut Criterion) {
+    let mut group = c.benchmark_group("from_str");
+    for (name, input) in INPUTS {
+        group.throughput(Throughput::Bytes(input.len() as u64));
+        group.bench_function(name, |b| {
+            b.iter(|| black_box(input).parse::<MediaTypeBuf>())
+        });
+    }
+    group.finish();
+}
+
+criterion_group!(benches, parse, from_str);
+criterion_main!(benches);
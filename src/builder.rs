@@ -0,0 +1,188 @@
+use super::{consts::names::_STAR, media_type::*, name::*, value::*};
+use alloc::{borrow::Cow, vec::Vec};
+
+/// A builder for [`MediaType`].
+///
+/// The top-level type and the subtype default to `*`.
+///
+/// ```
+/// use mediatype::{names::*, values::*, MediaTypeBuilder, Value};
+///
+/// let boundary = Value::new("dyEV84n7XNJ").unwrap();
+/// let multipart = MediaTypeBuilder::new()
+///     .ty(MULTIPART)
+///     .subty(FORM_DATA)
+///     .param(BOUNDARY, boundary)
+///     .param(CHARSET, UTF_8)
+///     .build();
+/// assert_eq!(
+///     multipart.to_string(),
+///     "multipart/form-data; boundary=dyEV84n7XNJ; charset=UTF-8"
+/// );
+/// ```
+#[derive(Debug, Clone)]
+pub struct MediaTypeBuilder<'a> {
+    ty: Name<'a>,
+    subty: Name<'a>,
+    suffix: Option<Name<'a>>,
+    params: Vec<(Name<'a>, Value<'a>)>,
+}
+
+impl<'a> MediaTypeBuilder<'a> {
+    /// Constructs a `MediaTypeBuilder`.
+    #[must_use]
+    pub const fn new() -> Self {
+        Self {
+            ty: _STAR,
+            subty: _STAR,
+            suffix: None,
+            params: Vec::new(),
+        }
+    }
+
+    /// Sets the top-level type.
+    #[must_use]
+    pub const fn ty(mut self, ty: Name<'a>) -> Self {
+        self.ty = ty;
+        self
+    }
+
+    /// Sets the subtype.
+    #[must_use]
+    pub const fn subty(mut self, subty: Name<'a>) -> Self {
+        self.subty = subty;
+        self
+    }
+
+    /// Sets the suffix.
+    #[must_use]
+    pub const fn suffix(mut self, suffix: Option<Name<'a>>) -> Self {
+        self.suffix = suffix;
+        self
+    }
+
+    /// Appends a parameter.
+    ///
+    /// Parameters keep the insertion order, and existing parameters with the same name
+    /// are not removed.
+    #[must_use]
+    pub fn param(mut self, name: Name<'a>, value: Value<'a>) -> Self {
+        self.params.push((name, value));
+        self
+    }
+
+    /// Constructs a [`MediaType`].
+    ///
+    /// A subtype containing `+` is split into the suffix like [`MediaType::new`]
+    /// if the suffix is not set.
+    #[must_use]
+    pub fn build(self) -> MediaType<'a> {
+        let params = if self.params.is_empty() {
+            Cow::Borrowed([].as_slice())
+        } else {
+            Cow::Owned(self.params)
+        };
+        let (subty, suffix) = split_subtype(self.subty, self.suffix);
+        MediaType::from_parts_unchecked(self.ty, subty, suffix, params)
+    }
+}
+
+/// Appends the parameters in the same way as [`MediaTypeBuilder::param`],
+/// so parameters with the same name are kept.
+impl<'a> Extend<(Name<'a>, Value<'a>)> for MediaTypeBuilder<'a> {
+    fn extend<I: IntoIterator<Item = (Name<'a>, Value<'a>)>>(&mut self, iter: I) {
+        self.params.extend(iter);
+    }
+}
+
+/// Collects the parameters into a builder of `*/*`.
+///
+/// Parameters with the same name are kept in the same way as [`MediaTypeBuilder::param`].
+///
+/// ```
+/// use mediatype::{names::*, values::*, MediaTypeBuilder};
+///
+/// let params = vec![(CHARSET, UTF_8), (FORMAT, FLOWED)];
+/// let media_type = params
+///     .into_iter()
+///     .collect::<MediaTypeBuilder>()
+///     .ty(TEXT)
+///     .subty(PLAIN)
+///     .build();
+/// assert_eq!(media_type.to_string(), "text/plain; charset=UTF-8; format=flowed");
+/// ```
+impl<'a> FromIterator<(Name<'a>, Value<'a>)> for MediaTypeBuilder<'a> {
+    fn from_iter<I: IntoIterator<Item = (Name<'a>, Value<'a>)>>(iter: I) -> Self {
+        let mut builder = Self::new();
+        builder.extend(iter);
+        builder
+    }
+}
+
+impl<'a> Default for MediaTypeBuilder<'a> {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{names::*, params::*, values::*};
+
+    #[test]
+    fn build() {
+        assert_eq!(MediaTypeBuilder::new().build().to_string(), "*/*");
+        assert_eq!(
+            MediaTypeBuilder::new().ty(TEXT).subty(PLAIN).build(),
+            MediaType::new(TEXT, PLAIN)
+        );
+        assert_eq!(
+            MediaTypeBuilder::new()
+                .ty(IMAGE)
+                .subty(SVG)
+                .suffix(Some(XML))
+                .param(CHARSET, UTF_8)
+                .build()
+                .to_string(),
+            "image/svg+xml; charset=UTF-8"
+        );
+        assert!(matches!(
+            MediaTypeBuilder::new().build().params,
+            Cow::Borrowed(_)
+        ));
+    }
+
+    #[test]
+    fn duplicate_params() {
+        let media_type = MediaTypeBuilder::new()
+            .ty(TEXT)
+            .subty(PLAIN)
+            .param(CHARSET, US_ASCII)
+            .param(FORMAT, FLOWED)
+            .param(CHARSET, UTF_8)
+            .build();
+        assert_eq!(
+            media_type.to_string(),
+            "text/plain; charset=US-ASCII; format=flowed; charset=UTF-8"
+        );
+        assert_eq!(media_type.get_param(CHARSET), Some(UTF_8));
+    }
+
+    #[test]
+    fn from_iter() {
+        let media_type = [(CHARSET, US_ASCII), (CHARSET, UTF_8)]
+            .into_iter()
+            .collect::<MediaTypeBuilder>()
+            .build();
+        assert_eq!(
+            media_type.to_string(),
+            "*/*; charset=US-ASCII; charset=UTF-8"
+        );
+        assert_eq!(media_type.get_param(CHARSET), Some(UTF_8));
+
+        let mut builder = MediaTypeBuilder::new().ty(TEXT).subty(PLAIN);
+        builder.extend([(FORMAT, FLOWED)]);
+        assert_eq!(builder.build().to_string(), "text/plain; format=flowed");
+    }
+}
@@ -0,0 +1,129 @@
+use super::{media_type::*, params::*};
+use core::{
+    cmp::Ordering,
+    hash::{Hash, Hasher},
+};
+
+/// A wrapper of [`MediaType`] with a total order for ordered collections like `BTreeMap`.
+///
+/// `MediaType` implements [`Eq`] and [`Hash`] but not [`Ord`]. `Canonical` orders
+/// media types by the type, the subtype, the suffix and the parameters sorted by
+/// [`Params::sorted`], so the order is consistent with `==`: names are compared
+/// case-insensitively, and the parameter order and overridden duplicates do not matter.
+///
+/// ```
+/// use mediatype::{Canonical, MediaType};
+/// use std::collections::BTreeMap;
+///
+/// let mut map = BTreeMap::new();
+/// map.insert(Canonical(MediaType::parse("text/plain; a=1; b=2").unwrap()), 1);
+/// map.insert(Canonical(MediaType::parse("TEXT/PLAIN; B=2; A=1").unwrap()), 2);
+/// assert_eq!(map.len(), 1);
+///
+/// let key = Canonical(MediaType::parse("text/plain; b=2; a=1").unwrap());
+/// assert_eq!(map.get(&key), Some(&2));
+/// ```
+#[derive(Debug, Clone)]
+pub struct Canonical<'a>(pub MediaType<'a>);
+
+impl<'a> From<MediaType<'a>> for Canonical<'a> {
+    fn from(media_type: MediaType<'a>) -> Self {
+        Self(media_type)
+    }
+}
+
+impl<'a> PartialEq for Canonical<'a> {
+    fn eq(&self, other: &Self) -> bool {
+        self.0 == other.0
+    }
+}
+
+impl<'a> Eq for Canonical<'a> {}
+
+impl<'a> PartialOrd for Canonical<'a> {
+    fn partial_cmp(&self, other: &Self) -> Option<Ordering> {
+        Some(self.cmp(other))
+    }
+}
+
+impl<'a> Ord for Canonical<'a> {
+    fn cmp(&self, other: &Self) -> Ordering {
+        let (a, b) = (&self.0, &other.0);
+        a.ty.cmp(&b.ty)
+            .then_with(|| a.subty.cmp(&b.subty))
+            .then_with(|| a.suffix.cmp(&b.suffix))
+            .then_with(|| a.params().sorted().cmp(b.params().sorted()))
+    }
+}
+
+impl<'a> Hash for Canonical<'a> {
+    fn hash<H: Hasher>(&self, state: &mut H) {
+        self.0.hash(state);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use alloc::vec::Vec;
+
+    fn canonical(s: &str) -> Canonical<'_> {
+        Canonical(MediaType::parse(s).unwrap())
+    }
+
+    #[test]
+    fn cmp() {
+        assert_eq!(
+            canonical("text/plain; a=1; b=2").cmp(&canonical("Text/Plain; b=2; A=\"1\"")),
+            Ordering::Equal
+        );
+        assert_eq!(
+            canonical("text/plain; a=0; a=1").cmp(&canonical("text/plain; a=1")),
+            Ordering::Equal
+        );
+        assert!(canonical("text/plain") < canonical("text/plain; a=1"));
+        assert!(canonical("text/plain; a=1") < canonical("text/plain; a=2"));
+        assert!(canonical("image/svg") < canonical("image/svg+xml"));
+        assert!(canonical("image/png") < canonical("text/plain"));
+
+        let mut list: Vec<_> = [
+            "text/plain; b=1",
+            "application/json",
+            "TEXT/PLAIN; A=1",
+            "text/html",
+        ]
+        .into_iter()
+        .map(canonical)
+        .collect();
+        list.sort();
+        assert_eq!(
+            list,
+            [
+                "application/json",
+                "text/html",
+                "text/plain; a=1",
+                "text/plain; b=1"
+            ]
+            .map(canonical)
+        );
+    }
+
+    #[test]
+    fn consistent_with_eq() {
+        let inputs = [
+            "text/plain",
+            "text/plain; charset=UTF-8",
+            "TEXT/PLAIN; CHARSET=\"UTF-8\"",
+            "text/plain; charset=utf-8",
+            "text/plain; format=flowed; charset=UTF-8",
+            "text/plain; charset=UTF-8; format=flowed",
+        ];
+        for a in inputs {
+            for b in inputs {
+                let (a, b) = (canonical(a), canonical(b));
+                assert_eq!(a == b, a.cmp(&b) == Ordering::Equal, "{a:?} {b:?}");
+                assert_eq!(a.cmp(&b), b.cmp(&a).reverse(), "{a:?} {b:?}");
+            }
+        }
+    }
+}
@@ -0,0 +1,68 @@
+/// Options for [`MediaType::compare_with`](crate::MediaType::compare_with).
+///
+/// The default options compare in the same way as `==`.
+///
+/// ```
+/// # use mediatype::{CompareOptions, MediaType};
+/// let a = MediaType::parse("text/plain; charset=utf-8; format=fixed").unwrap();
+/// let b = MediaType::parse("TEXT/PLAIN; format=fixed; charset=UTF-8").unwrap();
+/// assert!(!a.compare_with(&b, &CompareOptions::default()));
+///
+/// let options = CompareOptions::new().with_case_insensitive_values(true);
+/// assert!(a.compare_with(&b, &options));
+/// ```
+#[non_exhaustive]
+#[derive(Debug, Copy, Clone, Eq, PartialEq, Hash)]
+pub struct CompareOptions {
+    /// Compares only the essences and ignores the parameters entirely.
+    /// The default is `false`.
+    pub ignore_params: bool,
+
+    /// Compares the parameters as sets, where the last value of a duplicate name wins.
+    /// If `false`, the parameters must appear in the same order, including duplicates.
+    /// The default is `true`.
+    pub ignore_param_order: bool,
+
+    /// Compares the unquoted parameter values ASCII case-insensitively.
+    /// The default is `false`.
+    pub case_insensitive_values: bool,
+}
+
+impl CompareOptions {
+    /// Constructs a `CompareOptions` which compares in the same way as `==`.
+    #[must_use]
+    pub const fn new() -> Self {
+        Self {
+            ignore_params: false,
+            ignore_param_order: true,
+            case_insensitive_values: false,
+        }
+    }
+
+    /// Sets [`ignore_params`](Self::ignore_params).
+    #[must_use]
+    pub const fn with_ignore_params(mut self, ignore_params: bool) -> Self {
+        self.ignore_params = ignore_params;
+        self
+    }
+
+    /// Sets [`ignore_param_order`](Self::ignore_param_order).
+    #[must_use]
+    pub const fn with_ignore_param_order(mut self, ignore_param_order: bool) -> Self {
+        self.ignore_param_order = ignore_param_order;
+        self
+    }
+
+    /// Sets [`case_insensitive_values`](Self::case_insensitive_values).
+    #[must_use]
+    pub const fn with_case_insensitive_values(mut self, case_insensitive_values: bool) -> Self {
+        self.case_insensitive_values = case_insensitive_values;
+        self
+    }
+}
+
+impl Default for CompareOptions {
+    fn default() -> Self {
+        Self::new()
+    }
+}
@@ -1,4 +1,29 @@
+#[cfg(feature = "iana")]
+#[rustfmt::skip]
+mod iana;
 #[rustfmt::skip]
 pub mod names;
 #[rustfmt::skip]
 pub mod values;
+
+#[cfg(feature = "iana")]
+pub(crate) fn is_registered(
+    ty: crate::Name,
+    subty: crate::Name,
+    suffix: Option<crate::Name>,
+) -> bool {
+    use core::iter;
+
+    let suffix = suffix
+        .into_iter()
+        .flat_map(|suffix| iter::once(b'+').chain(suffix.as_str().bytes()));
+    let key = ty
+        .bytes()
+        .chain(iter::once(b'/'))
+        .chain(subty.bytes())
+        .chain(suffix)
+        .map(|b| b.to_ascii_lowercase());
+    iana::REGISTERED
+        .binary_search_by(|entry| entry.bytes().cmp(key.clone()))
+        .is_ok()
+}
@@ -2296,6 +2296,8 @@ pub mod x_ {
     pub const ICON: crate::Name = crate::Name::new_unchecked("x-icon");
     /// `x-javascript`
     pub const JAVASCRIPT: crate::Name = crate::Name::new_unchecked("x-javascript");
+    /// `x-matroska`
+    pub const MATROSKA: crate::Name = crate::Name::new_unchecked("x-matroska");
     /// `x-midi`
     pub const MIDI: crate::Name = crate::Name::new_unchecked("x-midi");
     /// `x-mixed-replace`
@@ -2304,8 +2306,12 @@ pub mod x_ {
     pub const MSGPACK: crate::Name = crate::Name::new_unchecked("x-msgpack");
     /// `x-msvideo`
     pub const MSVIDEO: crate::Name = crate::Name::new_unchecked("x-msvideo");
+    /// `x-ndjson`
+    pub const NDJSON: crate::Name = crate::Name::new_unchecked("x-ndjson");
     /// `x-pki-message`
     pub const PKI_MESSAGE: crate::Name = crate::Name::new_unchecked("x-pki-message");
+    /// `x-protobuf`
+    pub const PROTOBUF: crate::Name = crate::Name::new_unchecked("x-protobuf");
     /// `x-sh`
     pub const SH: crate::Name = crate::Name::new_unchecked("x-sh");
     /// `x-shockwave-flash`
@@ -2398,6 +2404,8 @@ pub const AMR_WB: crate::Name = crate::Name::new_unchecked("AMR-WB");
 pub const AMR_WB_PLUS: crate::Name = crate::Name::new_unchecked("amr-wb+");
 /// `andrew-inset`
 pub const ANDREW_INSET: crate::Name = crate::Name::new_unchecked("andrew-inset");
+/// `apng`
+pub const APNG: crate::Name = crate::Name::new_unchecked("apng");
 /// `appledouble`
 pub const APPLEDOUBLE: crate::Name = crate::Name::new_unchecked("appledouble");
 /// `applefile`
@@ -2750,6 +2758,8 @@ pub const FHIR: crate::Name = crate::Name::new_unchecked("fhir");
 pub const FHIRPATH: crate::Name = crate::Name::new_unchecked("fhirpath");
 /// `fits`
 pub const FITS: crate::Name = crate::Name::new_unchecked("fits");
+/// `flac`
+pub const FLAC: crate::Name = crate::Name::new_unchecked("flac");
 /// `flexfec`
 pub const FLEXFEC: crate::Name = crate::Name::new_unchecked("flexfec");
 /// `font`
@@ -2762,6 +2772,8 @@ pub const FONT_TDPFR: crate::Name = crate::Name::new_unchecked("font-tdpfr");
 pub const FONT_WOFF: crate::Name = crate::Name::new_unchecked("font-woff");
 /// `form-data`
 pub const FORM_DATA: crate::Name = crate::Name::new_unchecked("form-data");
+/// `x-www-form-urlencoded`
+pub const FORM_URLENCODED: crate::Name = crate::Name::new_unchecked("x-www-form-urlencoded");
 /// `format`
 pub const FORMAT: crate::Name = crate::Name::new_unchecked("format");
 /// `framework-attributes`
@@ -2818,6 +2830,8 @@ pub const GLTF_BUFFER: crate::Name = crate::Name::new_unchecked("gltf-buffer");
 pub const GML: crate::Name = crate::Name::new_unchecked("gml");
 /// `grammar-ref-list`
 pub const GRAMMAR_REF_LIST: crate::Name = crate::Name::new_unchecked("grammar-ref-list");
+/// `graphql-response`
+pub const GRAPHQL_RESPONSE: crate::Name = crate::Name::new_unchecked("graphql-response");
 /// `GSM`
 pub const GSM: crate::Name = crate::Name::new_unchecked("GSM");
 /// `GSM-EFR`
@@ -2964,6 +2978,8 @@ pub const JWK: crate::Name = crate::Name::new_unchecked("jwk");
 pub const JWK_SET: crate::Name = crate::Name::new_unchecked("jwk-set");
 /// `jwt`
 pub const JWT: crate::Name = crate::Name::new_unchecked("jwt");
+/// `jxl`
+pub const JXL: crate::Name = crate::Name::new_unchecked("jxl");
 /// `jxr`
 pub const JXR: crate::Name = crate::Name::new_unchecked("jxr");
 /// `jxrA`
@@ -3090,6 +3106,8 @@ pub const METS: crate::Name = crate::Name::new_unchecked("mets");
 pub const MF4: crate::Name = crate::Name::new_unchecked("MF4");
 /// `mhas`
 pub const MHAS: crate::Name = crate::Name::new_unchecked("mhas");
+/// `midi`
+pub const MIDI: crate::Name = crate::Name::new_unchecked("midi");
 /// `mikey`
 pub const MIKEY: crate::Name = crate::Name::new_unchecked("mikey");
 /// `mipc`
@@ -3236,6 +3254,8 @@ pub const PARALLEL: crate::Name = crate::Name::new_unchecked("parallel");
 pub const PARAMETERS: crate::Name = crate::Name::new_unchecked("parameters");
 /// `parityfec`
 pub const PARITYFEC: crate::Name = crate::Name::new_unchecked("parityfec");
+/// `partial`
+pub const PARTIAL: crate::Name = crate::Name::new_unchecked("partial");
 /// `passport`
 pub const PASSPORT: crate::Name = crate::Name::new_unchecked("passport");
 /// `patch-ops-error`
@@ -3376,6 +3396,8 @@ pub const RESOURCE_LISTS: crate::Name = crate::Name::new_unchecked("resource-lis
 pub const RESOURCE_LISTS_DIFF: crate::Name = crate::Name::new_unchecked("resource-lists-diff");
 /// `rfc`
 pub const RFC: crate::Name = crate::Name::new_unchecked("rfc");
+/// `rfc822`
+pub const RFC822: crate::Name = crate::Name::new_unchecked("rfc822");
 /// `rfc822-headers`
 pub const RFC822_HEADERS: crate::Name = crate::Name::new_unchecked("rfc822-headers");
 /// `richtext`
@@ -3524,12 +3546,18 @@ pub const SPEEX: crate::Name = crate::Name::new_unchecked("speex");
 pub const SPIRITS_EVENT: crate::Name = crate::Name::new_unchecked("spirits-event");
 /// `sql`
 pub const SQL: crate::Name = crate::Name::new_unchecked("sql");
+/// `sqlite3`
+pub const SQLITE3: crate::Name = crate::Name::new_unchecked("sqlite3");
 /// `srgs`
 pub const SRGS: crate::Name = crate::Name::new_unchecked("srgs");
 /// `sru`
 pub const SRU: crate::Name = crate::Name::new_unchecked("sru");
 /// `ssml`
 pub const SSML: crate::Name = crate::Name::new_unchecked("ssml");
+/// `start`
+pub const START: crate::Name = crate::Name::new_unchecked("start");
+/// `start-info`
+pub const START_INFO: crate::Name = crate::Name::new_unchecked("start-info");
 /// `step`
 pub const STEP: crate::Name = crate::Name::new_unchecked("step");
 /// `step-xml`
@@ -3604,6 +3632,8 @@ pub const TIMESTAMP_REPLY: crate::Name = crate::Name::new_unchecked("timestamp-r
 pub const TIMESTAMPED_DATA: crate::Name = crate::Name::new_unchecked("timestamped-data");
 /// `tlsrpt`
 pub const TLSRPT: crate::Name = crate::Name::new_unchecked("tlsrpt");
+/// `tlv`
+pub const TLV: crate::Name = crate::Name::new_unchecked("tlv");
 /// `tnauthlist`
 pub const TNAUTHLIST: crate::Name = crate::Name::new_unchecked("tnauthlist");
 /// `token-introspection`
@@ -3626,6 +3656,8 @@ pub const TTML: crate::Name = crate::Name::new_unchecked("ttml");
 pub const TURTLE: crate::Name = crate::Name::new_unchecked("turtle");
 /// `tve-trigger`
 pub const TVE_TRIGGER: crate::Name = crate::Name::new_unchecked("tve-trigger");
+/// `type`
+pub const TYPE: crate::Name = crate::Name::new_unchecked("type");
 /// `tzif`
 pub const TZIF: crate::Name = crate::Name::new_unchecked("tzif");
 /// `tzif-leap`
@@ -3760,6 +3792,8 @@ pub const XOP: crate::Name = crate::Name::new_unchecked("xop");
 pub const XSLT: crate::Name = crate::Name::new_unchecked("xslt");
 /// `xv`
 pub const XV: crate::Name = crate::Name::new_unchecked("xv");
+/// `yaml`
+pub const YAML: crate::Name = crate::Name::new_unchecked("yaml");
 /// `yang`
 pub const YANG: crate::Name = crate::Name::new_unchecked("yang");
 /// `yang-data`
@@ -1,35 +1,318 @@
-use std::{error, fmt};
+use core::fmt;
 
 /// Media-type format error.
+///
+/// Each variant holds the byte offset in the input where the error is detected.
 #[non_exhaustive]
 #[derive(Debug, Copy, Clone, Eq, PartialEq, Hash)]
 pub enum MediaTypeError {
+    /// The `/` between the type and the subtype is missing.
+    MissingSlash {
+        /// The byte offset of the error.
+        position: usize,
+    },
+    /// The top-level type name is empty.
+    EmptyType {
+        /// The byte offset of the error.
+        position: usize,
+    },
+    /// The subtype name is empty.
+    MissingSubtype {
+        /// The byte offset of the error.
+        position: usize,
+    },
+    /// The suffix name after `+` is empty.
+    EmptySuffix {
+        /// The byte offset of the error.
+        position: usize,
+    },
+    /// A parameter has no name before `=`.
+    MissingParamName {
+        /// The byte offset of the error.
+        position: usize,
+    },
     /// The top-level type name is not valid.
-    InvalidTypeName,
+    InvalidTypeName {
+        /// The byte offset of the error.
+        position: usize,
+    },
     /// The subtype name is not valid.
-    InvalidSubtypeName,
+    InvalidSubtypeName {
+        /// The byte offset of the error.
+        position: usize,
+    },
     /// The suffix name is not valid.
-    InvalidSuffix,
+    InvalidSuffix {
+        /// The byte offset of the error.
+        position: usize,
+    },
     /// The parameter syntax is not valid.
-    InvalidParams,
+    InvalidParams {
+        /// The byte offset of the error.
+        position: usize,
+    },
     /// An invalid parameter name is detected.
-    InvalidParamName,
+    InvalidParamName {
+        /// The byte offset of the error.
+        position: usize,
+    },
     /// An invalid parameter value is detected.
-    InvalidParamValue,
+    InvalidParamValue {
+        /// The byte offset of the error.
+        position: usize,
+    },
+    /// Unexpected characters follow the last valid token.
+    TrailingData {
+        /// The byte offset of the error.
+        position: usize,
+    },
+    /// A comment like `(comment)` of RFC 822 is detected.
+    ///
+    /// Comments are obsolete and not allowed in HTTP, so they are always rejected.
+    Comment {
+        /// The byte offset of the error.
+        position: usize,
+    },
+    /// A duplicate parameter name is detected.
+    DuplicateParam {
+        /// The byte offset of the error.
+        position: usize,
+    },
+    /// A wildcard `*` is used where a concrete media type is required.
+    Wildcard {
+        /// The byte offset of the error.
+        position: usize,
+    },
+    /// The input exceeds a limit of [`ParseOptions`](crate::ParseOptions).
+    TooLarge {
+        /// The byte offset of the error.
+        position: usize,
+    },
+    /// The input is not a valid UTF-8 string.
+    InvalidEncoding {
+        /// The byte offset of the error.
+        position: usize,
+    },
+}
+
+impl MediaTypeError {
+    /// Returns the byte offset in the input where the error is detected.
+    ///
+    /// Every current variant has an offset, so this always returns `Some`.
+    /// The `Option` leaves room for a future variant that is not tied to a
+    /// place in the input, which the `#[non_exhaustive]` enum allows adding.
+    ///
+    /// ```
+    /// # use mediatype::MediaType;
+    /// let err = MediaType::parse("text/plain; charset=UTF-8; format fixed").unwrap_err();
+    /// assert_eq!(err.position(), Some(33));
+    /// ```
+    #[must_use]
+    pub const fn position(&self) -> Option<usize> {
+        match *self {
+            Self::MissingSlash { position }
+            | Self::EmptyType { position }
+            | Self::MissingSubtype { position }
+            | Self::EmptySuffix { position }
+            | Self::MissingParamName { position }
+            | Self::InvalidTypeName { position }
+            | Self::InvalidSubtypeName { position }
+            | Self::InvalidSuffix { position }
+            | Self::InvalidParams { position }
+            | Self::InvalidParamName { position }
+            | Self::InvalidParamValue { position }
+            | Self::TrailingData { position }
+            | Self::Comment { position }
+            | Self::DuplicateParam { position }
+            | Self::Wildcard { position }
+            | Self::TooLarge { position }
+            | Self::InvalidEncoding { position } => Some(position),
+        }
+    }
+
+    pub(crate) const fn offset(self, offset: usize) -> Self {
+        match self {
+            Self::MissingSlash { position } => Self::MissingSlash {
+                position: position + offset,
+            },
+            Self::EmptyType { position } => Self::EmptyType {
+                position: position + offset,
+            },
+            Self::MissingSubtype { position } => Self::MissingSubtype {
+                position: position + offset,
+            },
+            Self::EmptySuffix { position } => Self::EmptySuffix {
+                position: position + offset,
+            },
+            Self::MissingParamName { position } => Self::MissingParamName {
+                position: position + offset,
+            },
+            Self::InvalidTypeName { position } => Self::InvalidTypeName {
+                position: position + offset,
+            },
+            Self::InvalidSubtypeName { position } => Self::InvalidSubtypeName {
+                position: position + offset,
+            },
+            Self::InvalidSuffix { position } => Self::InvalidSuffix {
+                position: position + offset,
+            },
+            Self::InvalidParams { position } => Self::InvalidParams {
+                position: position + offset,
+            },
+            Self::InvalidParamName { position } => Self::InvalidParamName {
+                position: position + offset,
+            },
+            Self::InvalidParamValue { position } => Self::InvalidParamValue {
+                position: position + offset,
+            },
+            Self::TrailingData { position } => Self::TrailingData {
+                position: position + offset,
+            },
+            Self::Comment { position } => Self::Comment {
+                position: position + offset,
+            },
+            Self::DuplicateParam { position } => Self::DuplicateParam {
+                position: position + offset,
+            },
+            Self::Wildcard { position } => Self::Wildcard {
+                position: position + offset,
+            },
+            Self::TooLarge { position } => Self::TooLarge {
+                position: position + offset,
+            },
+            Self::InvalidEncoding { position } => Self::InvalidEncoding {
+                position: position + offset,
+            },
+        }
+    }
+}
+
+impl MediaTypeError {
+    const fn description(&self) -> &'static str {
+        match self {
+            Self::MissingSlash { .. } => "missing `/` after the type",
+            Self::EmptyType { .. } => "empty type",
+            Self::MissingSubtype { .. } => "missing subtype",
+            Self::EmptySuffix { .. } => "empty suffix after `+`",
+            Self::MissingParamName { .. } => "missing parameter name before `=`",
+            Self::InvalidTypeName { .. } => "invalid type name",
+            Self::InvalidSubtypeName { .. } => "invalid subtype name",
+            Self::InvalidSuffix { .. } => "invalid suffix",
+            Self::InvalidParams { .. } => "invalid parameter syntax",
+            Self::InvalidParamName { .. } => "invalid parameter name",
+            Self::InvalidParamValue { .. } => "invalid parameter value",
+            Self::TrailingData { .. } => "unexpected trailing data",
+            Self::Comment { .. } => "comments are not allowed",
+            Self::DuplicateParam { .. } => "duplicate parameter name",
+            Self::Wildcard { .. } => "wildcard is not allowed",
+            Self::TooLarge { .. } => "input exceeds the limit",
+            Self::InvalidEncoding { .. } => "invalid UTF-8",
+        }
+    }
+
+    /// Returns a [`Display`](fmt::Display) implementation which also shows
+    /// the character of `input` at the position of the error.
+    ///
+    /// `input` should be the string which failed to be parsed.
+    ///
+    /// ```
+    /// # use mediatype::MediaType;
+    /// let input = "text/plain; char/set=UTF-8";
+    /// let err = MediaType::parse(input).unwrap_err();
+    /// assert_eq!(err.to_string(), "invalid parameter name at offset 16");
+    /// assert_eq!(
+    ///     err.display_with(input).to_string(),
+    ///     "invalid parameter name at offset 16: found '/'"
+    /// );
+    ///
+    /// let input = "text/";
+    /// let err = MediaType::parse(input).unwrap_err();
+    /// assert_eq!(
+    ///     err.display_with(input).to_string(),
+    ///     "missing subtype at offset 5: found end of input"
+    /// );
+    /// ```
+    #[must_use]
+    pub const fn display_with(self, input: &str) -> DisplayWithInput<'_> {
+        DisplayWithInput { err: self, input }
+    }
 }
 
 impl fmt::Display for MediaTypeError {
     fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
-        let msg = match self {
-            Self::InvalidTypeName => "Invalid type name",
-            Self::InvalidSubtypeName => "Invalid subtype name",
-            Self::InvalidSuffix => "Invalid suffix",
-            Self::InvalidParams => "Invalid params",
-            Self::InvalidParamName => "Invalid param name",
-            Self::InvalidParamValue => "Invalid param value",
+        f.write_str(self.description())?;
+        if let Some(position) = self.position() {
+            write!(f, " at offset {position}")?;
+        }
+        Ok(())
+    }
+}
+
+/// An error with the input, which is returned by [`MediaTypeError::display_with`].
+#[derive(Debug, Copy, Clone)]
+pub struct DisplayWithInput<'a> {
+    err: MediaTypeError,
+    input: &'a str,
+}
+
+impl<'a> fmt::Display for DisplayWithInput<'a> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        self.err.fmt(f)?;
+        let Some(position) = self.err.position() else {
+            return Ok(());
         };
-        f.write_str(msg)
+        match self.input.get(position..).map(|rest| rest.chars().next()) {
+            Some(Some(c)) => write!(f, ": found {c:?}"),
+            Some(None) => f.write_str(": found end of input"),
+            None => Ok(()),
+        }
     }
 }
 
-impl error::Error for MediaTypeError {}
+#[cfg(feature = "std")]
+#[cfg_attr(docsrs, doc(cfg(feature = "std")))]
+impl std::error::Error for MediaTypeError {}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::collections::HashSet;
+
+    #[test]
+    fn derives() {
+        let err = MediaTypeError::InvalidParams { position: 3 };
+        let cloned = err;
+        assert_eq!(err, cloned.clone());
+        assert_ne!(err, MediaTypeError::InvalidParams { position: 4 });
+
+        let set = HashSet::from([err, MediaTypeError::TrailingData { position: 3 }]);
+        assert!(set.contains(&MediaTypeError::InvalidParams { position: 3 }));
+        assert!(!set.contains(&MediaTypeError::Comment { position: 3 }));
+    }
+
+    #[test]
+    fn display() {
+        assert_eq!(
+            MediaTypeError::InvalidParamName { position: 12 }.to_string(),
+            "invalid parameter name at offset 12"
+        );
+        assert_eq!(
+            MediaTypeError::MissingSlash { position: 4 }.to_string(),
+            "missing `/` after the type at offset 4"
+        );
+
+        let err = MediaTypeError::InvalidSubtypeName { position: 6 };
+        assert_eq!(
+            err.display_with("image/\u{e9}").to_string(),
+            "invalid subtype name at offset 6: found 'é'"
+        );
+        assert_eq!(
+            err.display_with("text/éa").to_string(),
+            "invalid subtype name at offset 6"
+        );
+        assert_eq!(
+            err.display_with("text").to_string(),
+            "invalid subtype name at offset 6"
+        );
+    }
+}
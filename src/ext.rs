@@ -0,0 +1,143 @@
+#![cfg(feature = "ext")]
+
+use super::{media_type, media_type::*};
+
+/// Common file extensions on the web.
+///
+/// The first extension of each media type is the preferred one.
+static EXTENSIONS: [(&str, MediaType<'static>); 40] = [
+    ("html", media_type!(TEXT / HTML)),
+    ("htm", media_type!(TEXT / HTML)),
+    ("css", media_type!(TEXT / CSS)),
+    ("js", media_type!(TEXT / JAVASCRIPT)),
+    ("mjs", media_type!(TEXT / JAVASCRIPT)),
+    ("txt", media_type!(TEXT / PLAIN)),
+    ("csv", media_type!(TEXT / CSV)),
+    ("md", media_type!(TEXT / MARKDOWN)),
+    ("markdown", media_type!(TEXT / MARKDOWN)),
+    ("json", media_type!(APPLICATION / JSON)),
+    ("xml", media_type!(APPLICATION / XML)),
+    ("pdf", media_type!(APPLICATION / PDF)),
+    ("zip", media_type!(APPLICATION / ZIP)),
+    ("gz", media_type!(APPLICATION / GZIP)),
+    ("wasm", media_type!(APPLICATION / WASM)),
+    ("rtf", media_type!(APPLICATION / RTF)),
+    ("bin", media_type!(APPLICATION / OCTET_STREAM)),
+    ("png", media_type!(IMAGE / PNG)),
+    ("jpg", media_type!(IMAGE / JPEG)),
+    ("jpeg", media_type!(IMAGE / JPEG)),
+    ("gif", media_type!(IMAGE / GIF)),
+    ("webp", media_type!(IMAGE / WEBP)),
+    ("avif", media_type!(IMAGE / AVIF)),
+    ("svg", media_type!(IMAGE / SVG + XML)),
+    ("ico", media_type!(IMAGE / vnd::MICROSOFT_ICON)),
+    ("bmp", media_type!(IMAGE / BMP)),
+    ("tiff", media_type!(IMAGE / TIFF)),
+    ("tif", media_type!(IMAGE / TIFF)),
+    ("woff", media_type!(FONT / WOFF)),
+    ("woff2", media_type!(FONT / WOFF2)),
+    ("ttf", media_type!(FONT / TTF)),
+    ("otf", media_type!(FONT / OTF)),
+    ("mp3", media_type!(AUDIO / MPEG)),
+    ("ogg", media_type!(AUDIO / OGG)),
+    ("opus", media_type!(AUDIO / OPUS)),
+    ("wav", media_type!(AUDIO / WAV)),
+    ("aac", media_type!(AUDIO / AAC)),
+    ("mp4", media_type!(VIDEO / MP4)),
+    ("webm", media_type!(VIDEO / WEBM)),
+    ("mpeg", media_type!(VIDEO / MPEG)),
+];
+
+impl MediaType<'static> {
+    /// Returns the media type of a common file extension.
+    ///
+    /// The extension is compared case-insensitively and must not have the leading `.`.
+    /// The table only covers the common types on the web.
+    ///
+    /// ```
+    /// # use mediatype::{media_type, MediaType};
+    /// assert_eq!(MediaType::from_extension("png"), Some(media_type!(IMAGE/PNG)));
+    /// assert_eq!(MediaType::from_extension("SVG"), Some(media_type!(IMAGE/SVG+XML)));
+    /// assert_eq!(MediaType::from_extension(".png"), None);
+    /// ```
+    #[cfg_attr(docsrs, doc(cfg(feature = "ext")))]
+    #[must_use]
+    pub fn from_extension(ext: &str) -> Option<Self> {
+        EXTENSIONS
+            .iter()
+            .find(|(key, _)| key.eq_ignore_ascii_case(ext))
+            .map(|(_, media_type)| media_type.clone())
+    }
+}
+
+impl<'a> MediaType<'a> {
+    /// Returns the preferred file extension of the media type.
+    ///
+    /// Names are compared case-insensitively and parameters are ignored.
+    ///
+    /// ```
+    /// # use mediatype::MediaType;
+    /// let jpeg = MediaType::parse("image/jpeg; q=0.8").unwrap();
+    /// assert_eq!(jpeg.preferred_extension(), Some("jpg"));
+    /// assert_eq!(MediaType::parse("image/x-unknown").unwrap().preferred_extension(), None);
+    /// ```
+    #[cfg_attr(docsrs, doc(cfg(feature = "ext")))]
+    #[must_use]
+    pub fn preferred_extension(&self) -> Option<&'static str> {
+        EXTENSIONS
+            .iter()
+            .find(|(_, media_type)| self.essence_eq(media_type))
+            .map(|(ext, _)| *ext)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn from_extension() {
+        assert_eq!(
+            MediaType::from_extension("html"),
+            Some(media_type!(TEXT / HTML))
+        );
+        assert_eq!(
+            MediaType::from_extension("JPEG"),
+            Some(media_type!(IMAGE / JPEG))
+        );
+        assert_eq!(MediaType::from_extension(""), None);
+        assert_eq!(MediaType::from_extension("exe"), None);
+    }
+
+    #[test]
+    fn preferred_extension() {
+        assert_eq!(
+            MediaType::parse("TEXT/HTML; charset=UTF-8")
+                .unwrap()
+                .preferred_extension(),
+            Some("html")
+        );
+        assert_eq!(
+            MediaType::parse("image/svg+xml")
+                .unwrap()
+                .preferred_extension(),
+            Some("svg")
+        );
+        assert_eq!(
+            MediaType::parse("image/svg").unwrap().preferred_extension(),
+            None
+        );
+    }
+
+    #[test]
+    fn roundtrip() {
+        for (ext, media_type) in &EXTENSIONS {
+            assert_eq!(MediaType::from_extension(ext).as_ref(), Some(media_type));
+            let preferred = media_type.preferred_extension().unwrap();
+            assert_eq!(
+                MediaType::from_extension(preferred).as_ref(),
+                Some(media_type)
+            );
+        }
+    }
+}
@@ -0,0 +1,70 @@
+#![cfg(feature = "http")]
+
+use super::{error::*, media_type::*};
+use alloc::string::ToString;
+use http::header::{HeaderValue, InvalidHeaderValue};
+
+#[cfg_attr(docsrs, doc(cfg(feature = "http")))]
+impl<'a> TryFrom<&'a HeaderValue> for MediaType<'a> {
+    type Error = MediaTypeError;
+
+    fn try_from(value: &'a HeaderValue) -> Result<Self, Self::Error> {
+        Self::try_from(value.as_bytes())
+    }
+}
+
+#[cfg_attr(docsrs, doc(cfg(feature = "http")))]
+impl<'a> TryFrom<MediaType<'a>> for HeaderValue {
+    type Error = InvalidHeaderValue;
+
+    fn try_from(value: MediaType<'a>) -> Result<Self, Self::Error> {
+        Self::from_bytes(value.to_string().as_bytes())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{names::*, values::*};
+
+    #[test]
+    fn from_header_value() {
+        let header = HeaderValue::from_static("image/svg+xml; charset=UTF-8");
+        assert_eq!(
+            MediaType::try_from(&header),
+            Ok(MediaType::from_parts(
+                IMAGE,
+                SVG,
+                Some(XML),
+                &[(CHARSET, UTF_8)]
+            ))
+        );
+
+        let header = HeaderValue::from_static("image");
+        assert_eq!(
+            MediaType::try_from(&header),
+            Err(MediaTypeError::MissingSlash { position: 5 })
+        );
+
+        let header = HeaderValue::from_bytes(b"text/plain; charset=\"\xff\"").unwrap();
+        assert_eq!(
+            MediaType::try_from(&header),
+            Err(MediaTypeError::InvalidEncoding { position: 21 })
+        );
+    }
+
+    #[test]
+    fn to_header_value() {
+        let media_type = MediaType::parse("text/plain;charset=\"Hello, world!\"").unwrap();
+        assert_eq!(
+            HeaderValue::try_from(media_type).unwrap(),
+            "text/plain; charset=\"Hello, world!\""
+        );
+
+        let media_type = MediaType::parse("text/plain; title=\"\u{2713}\"").unwrap();
+        assert_eq!(
+            HeaderValue::try_from(media_type).unwrap().as_bytes(),
+            "text/plain; title=\"\u{2713}\"".as_bytes()
+        );
+    }
+}
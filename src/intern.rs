@@ -0,0 +1,177 @@
+#![cfg(feature = "intern")]
+
+use super::{media_type::*, name::*, value::*};
+use std::{
+    borrow::Cow,
+    collections::HashMap,
+    hash::{BuildHasher, RandomState},
+    sync::{Mutex, OnceLock, PoisonError},
+};
+
+/// Interned media types grouped by their hashes.
+struct Interner {
+    hasher: RandomState,
+    types: Mutex<HashMap<u64, Vec<&'static MediaType<'static>>>>,
+}
+
+static INTERNER: OnceLock<Interner> = OnceLock::new();
+
+impl<'a> MediaType<'a> {
+    /// Returns a shared `'static` copy of the media type.
+    ///
+    /// The first call for a media type copies it into a global table,
+    /// and subsequent calls for the identical media type return the same instance
+    /// after a lookup, without allocating.
+    /// Media types are identical if their names, including the letter case,
+    /// and their parameters, including the order, are the same.
+    /// Media types which are `==` but not identical are interned separately.
+    ///
+    /// Interned media types are never freed, so the memory grows with each distinct
+    /// media type. Only intern a bounded set of types, such as the ones known
+    /// to the application, and not arbitrary input from clients.
+    ///
+    /// ```
+    /// # use mediatype::MediaType;
+    /// let a = MediaType::parse("application/json; charset=UTF-8").unwrap().intern();
+    /// let b = MediaType::parse("application/json; charset=UTF-8").unwrap().intern();
+    /// assert!(core::ptr::eq(a, b));
+    ///
+    /// let c = MediaType::parse("Application/JSON; charset=UTF-8").unwrap().intern();
+    /// assert!(!core::ptr::eq(a, c));
+    /// assert_eq!(a, c);
+    /// ```
+    #[cfg_attr(docsrs, doc(cfg(feature = "intern")))]
+    #[must_use]
+    pub fn intern(&self) -> &'static MediaType<'static> {
+        let interner = INTERNER.get_or_init(|| Interner {
+            hasher: RandomState::new(),
+            types: Mutex::default(),
+        });
+        let hash = interner.hasher.hash_one(self);
+        let mut types = interner
+            .types
+            .lock()
+            .unwrap_or_else(PoisonError::into_inner);
+        let bucket = types.entry(hash).or_default();
+        if let Some(interned) = bucket.iter().find(|interned| identical(interned, self)) {
+            return interned;
+        }
+
+        let interned = leak(self);
+        bucket.push(interned);
+        interned
+    }
+}
+
+/// Copies the names and the values of `media_type` into a single leaked string as they are,
+/// so that the copy is identical to `media_type`.
+fn leak(media_type: &MediaType) -> &'static MediaType<'static> {
+    let mut rest: &'static str = [media_type.ty.as_str(), media_type.subty.as_str()]
+        .into_iter()
+        .chain(media_type.suffix.map(|suffix| suffix.as_str()))
+        .chain(
+            media_type
+                .params
+                .iter()
+                .flat_map(|(name, value)| [name.as_str(), value.as_str()]),
+        )
+        .collect::<String>()
+        .leak();
+    let mut take = |len| {
+        let (s, tail) = rest.split_at(len);
+        rest = tail;
+        s
+    };
+
+    let ty = Name::new_unchecked(take(media_type.ty.len()));
+    let subty = Name::new_unchecked(take(media_type.subty.len()));
+    let suffix = media_type
+        .suffix
+        .map(|suffix| Name::new_unchecked(take(suffix.len())));
+    let params = media_type
+        .params
+        .iter()
+        .map(|(name, value)| {
+            let name = Name::new_unchecked(take(name.len()));
+            if value.is_absent() {
+                (name, Value::ABSENT)
+            } else {
+                (name, Value::new_unchecked(take(value.len())))
+            }
+        })
+        .collect::<Vec<_>>();
+    let params = if params.is_empty() {
+        Cow::Borrowed([].as_slice())
+    } else {
+        Cow::Owned(params)
+    };
+    Box::leak(Box::new(MediaType::from_parts_unchecked(
+        ty, subty, suffix, params,
+    )))
+}
+
+fn identical(a: &MediaType, b: &MediaType) -> bool {
+    a.ty.as_str() == b.ty.as_str()
+        && a.subty.as_str() == b.subty.as_str()
+        && a.suffix.map(|suffix| suffix.as_str()) == b.suffix.map(|suffix| suffix.as_str())
+        && a.params.len() == b.params.len()
+        && a.params
+            .iter()
+            .zip(b.params.iter())
+            .all(|(a, b)| a.0.as_str() == b.0.as_str() && a.1.as_str() == b.1.as_str())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{media_type, names::*, values::*, Name, ReadParams, Value};
+
+    #[test]
+    fn intern() {
+        let input = String::from("image/svg+xml; charset=UTF-8; a=\"b c\"");
+        let interned = MediaType::parse(&input).unwrap().intern();
+        drop(input);
+        assert_eq!(
+            interned.to_string(),
+            "image/svg+xml; charset=UTF-8; a=\"b c\""
+        );
+        assert_eq!(interned.get_param(CHARSET), Some(UTF_8));
+
+        let params = [
+            (CHARSET, UTF_8),
+            (Name::new("a").unwrap(), Value::new("\"b c\"").unwrap()),
+        ];
+        let svg = MediaType::from_parts(IMAGE, SVG, Some(XML), &params);
+        assert!(core::ptr::eq(svg.intern(), interned));
+
+        let text_plain = media_type!(TEXT / PLAIN).intern();
+        assert!(core::ptr::eq(
+            text_plain,
+            media_type!(TEXT / PLAIN).intern()
+        ));
+        assert!(!core::ptr::eq(
+            text_plain,
+            media_type!(TEXT / PLAIN; CHARSET = UTF_8).intern()
+        ));
+        assert!(!core::ptr::eq(
+            text_plain,
+            MediaType::parse("TEXT/PLAIN").unwrap().intern()
+        ));
+    }
+
+    #[test]
+    fn intern_folded() {
+        let mut folded = MediaType::new(IMAGE, SVG);
+        folded.subty = Name::new("svg+xml").unwrap();
+        let interned = folded.intern();
+        assert_eq!(interned.subty.as_str(), "svg+xml");
+        assert_eq!(interned.suffix, None);
+        for _ in 0..3 {
+            assert!(core::ptr::eq(folded.clone().intern(), interned));
+        }
+        assert!(!core::ptr::eq(
+            MediaType::parse("image/svg+xml").unwrap().intern(),
+            interned
+        ));
+    }
+}
@@ -1,7 +1,7 @@
 //! This crate provides two media type structs: [`MediaType`] and [`MediaTypeBuf`].
 //!
 //! - [`MediaType`] does not copy data during parsing
-//!     and borrows the original string. It is also const-constructible.
+//!   and borrows the original string. It is also const-constructible.
 //! - [`MediaTypeBuf`] is an owned  and immutable version of [`MediaType`].
 //!
 //! [`MadiaType`]: ./struct.MediaType.html
@@ -38,29 +38,57 @@
 //! assert_eq!(upper.subty(), "Plain");
 //! ```
 
+#![cfg_attr(not(any(feature = "std", test)), no_std)]
 #![forbid(unsafe_code)]
 #![forbid(clippy::all)]
 #![cfg_attr(docsrs, feature(doc_cfg))]
 
+extern crate alloc;
+
+mod builder;
+mod canonical;
+mod compare_options;
 mod consts;
 mod error;
+mod ext;
+mod http;
+mod intern;
+#[doc(hidden)]
+pub mod literal;
 mod media_type;
 mod media_type_buf;
 mod media_type_list;
+mod media_type_parser;
+#[cfg(target_has_atomic = "ptr")]
+mod media_type_shared;
+mod mime;
 mod name;
 mod params;
 mod parse;
+mod parse_options;
+mod registration_tree;
 mod serde;
 mod value;
+mod verbatim;
+mod whatwg;
 
+pub use builder::*;
+pub use canonical::*;
+pub use compare_options::*;
 pub use consts::*;
 pub use error::*;
 pub use media_type::*;
 pub use media_type_buf::*;
 pub use media_type_list::*;
+pub use media_type_parser::*;
+#[cfg(target_has_atomic = "ptr")]
+pub use media_type_shared::*;
 pub use name::*;
 pub use params::*;
+pub use parse_options::*;
+pub use registration_tree::*;
 pub use value::*;
+pub use verbatim::*;
 
 /// Convenient macro to construct a [`MediaType`].
 ///
@@ -81,8 +109,31 @@ pub use value::*;
 ///     "application/vnd.openstreetmap.data+xml"
 /// );
 /// ```
+///
+/// A string literal is parsed at compile time like [`MediaType::parse`],
+/// and the result can be used as a constant.
+///
+/// ```
+/// # use mediatype::{media_type, MediaType};
+/// const IMAGE_PNG: MediaType = media_type!("image/png; charset=utf-8");
+/// assert_eq!(IMAGE_PNG, MediaType::parse("image/png; charset=utf-8").unwrap());
+/// ```
+///
+/// A malformed literal fails to compile.
+///
+/// ```compile_fail
+/// # use mediatype::{media_type, MediaType};
+/// const INVALID: MediaType = media_type!("image/png charset=utf-8");
+/// ```
 #[macro_export]
 macro_rules! media_type {
+    ($s:literal) => {{
+        const PARTS: $crate::literal::LiteralParts<'static, { $crate::literal::param_count($s) }> =
+            $crate::literal::parse($s);
+        const MEDIA_TYPE: $crate::MediaType<'static> =
+            $crate::MediaType::from_parts(PARTS.ty, PARTS.subty, PARTS.suffix, &PARTS.params);
+        MEDIA_TYPE
+    }};
     ($ty:ident / $prefix:ident $(:: $subty:ident)* $(;$name:ident = $value:ident)*) => {
         $crate::MediaType::from_parts(
             $crate::names::$ty,
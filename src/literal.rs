@@ -0,0 +1,348 @@
+//! Compile-time parsing of string literals for [`media_type!`](crate::media_type).
+//!
+//! The items are public only to be used by the macro.
+
+use super::{name::*, parse::*, value::*};
+
+/// The parts of a media type literal with `N` parameters.
+#[derive(Debug, Copy, Clone)]
+pub struct LiteralParts<'a, const N: usize> {
+    pub ty: Name<'a>,
+    pub subty: Name<'a>,
+    pub suffix: Option<Name<'a>>,
+    pub params: [(Name<'a>, Value<'a>); N],
+}
+
+/// Returns the number of parameters of a media type literal.
+///
+/// # Panics
+///
+/// Panics if `s` is not a valid media type.
+#[must_use]
+pub const fn param_count(s: &str) -> usize {
+    let s = s.as_bytes();
+    let mut offset = parse_essence(s)[3];
+    let mut count = 0;
+    while let Some(param) = parse_param(s, offset) {
+        offset = param[3];
+        count += 1;
+    }
+    count
+}
+
+/// Parses a media type literal with `N` parameters,
+/// as [`MediaType::parse`](crate::MediaType::parse) does.
+///
+/// # Panics
+///
+/// Panics if `s` is not a valid media type, or it does not have `N` parameters.
+#[must_use]
+pub const fn parse<const N: usize>(s: &str) -> LiteralParts<'_, N> {
+    let bytes = s.as_bytes();
+    let [ty_end, subty_end, suffix_start, end] = parse_essence(bytes);
+    let ty_start = essence_start(bytes);
+
+    let mut params = [(Name::new_unchecked(""), Value::new_unchecked("")); N];
+    let mut offset = end;
+    let mut i = 0;
+    while let Some(param) = parse_param(bytes, offset) {
+        if i == N {
+            panic!("unexpected number of parameters");
+        }
+        params[i] = (
+            Name::new_unchecked(substr(s, param[0], param[1])),
+            Value::new_unchecked(substr(s, param[2], param[3])),
+        );
+        offset = param[3];
+        i += 1;
+    }
+    if i != N {
+        panic!("unexpected number of parameters");
+    }
+
+    LiteralParts {
+        ty: Name::new_unchecked(substr(s, ty_start, ty_end)),
+        subty: Name::new_unchecked(substr(s, ty_end + 1, subty_end)),
+        suffix: if suffix_start < end {
+            Some(Name::new_unchecked(substr(s, suffix_start, end)))
+        } else {
+            None
+        },
+        params,
+    }
+}
+
+/// Returns the end of the type, the end of the subtype, the start of the suffix
+/// and the end of the essence. Without a suffix, the suffix starts at the end.
+const fn parse_essence(s: &[u8]) -> [usize; 4] {
+    let start = essence_start(s);
+    let ty_end = restricted_end(s, start);
+    if ty_end == s.len() || s[ty_end] != b'/' {
+        if ty_end == start && (ty_end == s.len() || s[ty_end] == b'/') {
+            panic!("empty type");
+        }
+        if ty_end == s.len() {
+            panic!("missing slash");
+        }
+        panic!("invalid type name");
+    }
+    if !is_valid_name(s, start, ty_end) {
+        panic!("invalid type name");
+    }
+
+    let end = restricted_end(s, ty_end + 1);
+    let mut plus = end;
+    while plus > ty_end + 1 && s[plus - 1] != b'+' {
+        plus -= 1;
+    }
+    let (subty_end, suffix_start) = if plus > ty_end + 1 {
+        (plus - 1, plus)
+    } else {
+        (end, end)
+    };
+
+    if subty_end == ty_end + 1 {
+        panic!("missing subtype");
+    }
+    if !is_valid_name(s, ty_end + 1, subty_end) {
+        panic!("invalid subtype name");
+    }
+    if subty_end < end {
+        if suffix_start == end {
+            panic!("empty suffix");
+        }
+        if !is_valid_name(s, suffix_start, end) {
+            panic!("invalid suffix");
+        }
+    }
+    [ty_end, subty_end, suffix_start, end]
+}
+
+/// Returns the ranges of the name and the value of the parameter after `offset`,
+/// or `None` if only whitespace and an optional `;` remain.
+const fn parse_param(s: &[u8], offset: usize) -> Option<[usize; 4]> {
+    let offset = trim_ows(s, offset);
+    if offset == s.len() {
+        return None;
+    }
+    if s[offset] == b'(' {
+        panic!("comments are not allowed");
+    }
+    if s[offset] != b';' {
+        panic!("trailing data");
+    }
+
+    let name_start = trim_ows(s, offset + 1);
+    if name_start == s.len() {
+        return None;
+    }
+    if s[name_start] == b'(' {
+        panic!("comments are not allowed");
+    }
+    let mut name_end = name_start;
+    while name_end < s.len() && s[name_end] != b'=' {
+        name_end += 1;
+    }
+    if name_end == s.len() {
+        panic!("invalid parameters");
+    }
+    if name_end == name_start {
+        panic!("missing parameter name");
+    }
+    if !is_valid_name(s, name_start, name_end) {
+        panic!("invalid parameter name");
+    }
+
+    let value_start = name_end + 1;
+    let value_end = if value_start < s.len() && s[value_start] == b'"' {
+        quoted_end(s, value_start + 1)
+    } else {
+        restricted_end(s, value_start)
+    };
+    if value_end == value_start
+        && value_end < s.len()
+        && !matches!(s[value_end], b' ' | b'\t' | b';' | b'(')
+    {
+        panic!("invalid parameters");
+    }
+    Some([name_start, name_end, value_start, value_end])
+}
+
+/// Returns the end of the quoted string including the closing quote.
+const fn quoted_end(s: &[u8], mut i: usize) -> usize {
+    let mut escaped = false;
+    while i < s.len() {
+        let b = s[i];
+        if b.is_ascii_control() && b != b'\t' {
+            panic!("invalid parameter value");
+        } else if escaped {
+            escaped = false;
+        } else if b == b'\\' {
+            escaped = true;
+        } else if b == b'"' {
+            return i + 1;
+        }
+        i += 1;
+    }
+    panic!("unterminated quoted string");
+}
+
+const fn is_valid_name(s: &[u8], start: usize, end: usize) -> bool {
+    start < end
+        && end - start <= Name::MAX_LENGTH
+        && (s[start].is_ascii_alphanumeric() || s[start] == b'*')
+        && restricted_end(s, start) >= end
+}
+
+const fn restricted_end(s: &[u8], mut i: usize) -> usize {
+    while i < s.len() && is_restricted_byte(s[i]) {
+        i += 1;
+    }
+    i
+}
+
+/// Skips a leading UTF-8 byte order mark and whitespace.
+const fn essence_start(s: &[u8]) -> usize {
+    if let [0xef, 0xbb, 0xbf, ..] = s {
+        trim_ows(s, 3)
+    } else {
+        trim_ows(s, 0)
+    }
+}
+
+const fn trim_ows(s: &[u8], mut i: usize) -> usize {
+    while i < s.len() && (s[i] == b' ' || s[i] == b'\t') {
+        i += 1;
+    }
+    i
+}
+
+/// Returns `&s[start..end]`. Both ends are next to ASCII characters.
+const fn substr(s: &str, start: usize, end: usize) -> &str {
+    let (bytes, _) = s.as_bytes().split_at(end);
+    let (_, bytes) = bytes.split_at(start);
+    match core::str::from_utf8(bytes) {
+        Ok(s) => s,
+        Err(_) => panic!("invalid char boundary"),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{media_type, MediaType};
+    use std::{panic, vec::Vec};
+
+    type Parts<'a> = (&'a str, &'a str, Option<&'a str>, Vec<(&'a str, Value<'a>)>);
+
+    fn literal_parts(s: &str) -> Parts<'_> {
+        fn strs<const N: usize>(parts: LiteralParts<'_, N>) -> Parts<'_> {
+            (
+                parts.ty.as_str(),
+                parts.subty.as_str(),
+                parts.suffix.map(|suffix| suffix.as_str()),
+                parts
+                    .params
+                    .iter()
+                    .map(|(name, value)| (name.as_str(), *value))
+                    .collect(),
+            )
+        }
+        match param_count(s) {
+            0 => strs(parse::<0>(s)),
+            1 => strs(parse::<1>(s)),
+            2 => strs(parse::<2>(s)),
+            3 => strs(parse::<3>(s)),
+            n => panic!("{n} parameters are not covered"),
+        }
+    }
+
+    fn parsed_parts(s: &str) -> Option<Parts<'_>> {
+        let media_type = MediaType::parse(s).ok()?;
+        Some((
+            media_type.ty.as_str(),
+            media_type.subty.as_str(),
+            media_type.suffix.map(|suffix| suffix.as_str()),
+            media_type
+                .params
+                .iter()
+                .map(|(name, value)| (name.as_str(), *value))
+                .collect(),
+        ))
+    }
+
+    #[test]
+    fn same_as_parse() {
+        for input in [
+            "text/plain",
+            "*/*",
+            "text/*",
+            " \ttext/plain \t",
+            "image/svg+xml",
+            "application/vnd.foo+zip",
+            "text/plain;charset=UTF-8",
+            "text/plain; charset=UTF-8 ; ",
+            "text/plain; a=1; b=\"x; \\\"y\\\" \u{fc}\"; c=",
+            "text/plain; flag",
+            "text/plain; flag ; a=1",
+            "text/plain; a=1;",
+            "",
+            "text",
+            "text/",
+            "/plain",
+            "text//plain",
+            "te xt/plain",
+            "text/pl ain",
+            "text/plain garbage",
+            "text/plain(comment)",
+            "text/plain; (comment) a=1",
+            "text/plain; a=(1)",
+            "text/plain;;",
+            "text/plain; =1",
+            "text/plain; a b=1",
+            "text/plain; a=1 b",
+            "text/plain; a=1,",
+            "text/plain; charset==UTF-8",
+            "text/plain; a=\"1",
+            "text/plain; a=\"\n\"",
+            "text/plain; charset UTF-8",
+            "text/plain; a=1; b=2; c=3",
+            "image/svg+",
+            "image/+xml",
+            "image/svg+xml+zip",
+            "image/svg++xml",
+            "text/pl\u{e4}in",
+            "-text/plain",
+            "\u{feff}text/plain",
+        ] {
+            let literal = panic::catch_unwind(|| literal_parts(input)).ok();
+            assert_eq!(literal, parsed_parts(input), "{input:?}");
+        }
+    }
+
+    #[test]
+    fn literal() {
+        const IMAGE_SVG: MediaType = media_type!("image/svg+xml; charset=UTF-8");
+        assert_eq!(
+            IMAGE_SVG,
+            MediaType::parse("image/svg+xml; charset=UTF-8").unwrap()
+        );
+
+        for (literal, input) in [
+            (media_type!("*/*"), "*/*"),
+            (media_type!(" text/plain ;"), " text/plain ;"),
+            (
+                media_type!("application/vnd.foo+xml+zip"),
+                "application/vnd.foo+xml+zip",
+            ),
+            (
+                media_type!("text/plain;a=1;\tb=\"x; \\\"y\\\" ü\" ; c="),
+                "text/plain;a=1;\tb=\"x; \\\"y\\\" ü\" ; c=",
+            ),
+        ] {
+            let parsed = MediaType::parse(input).unwrap();
+            assert_eq!(literal, parsed, "{input:?}");
+            assert_eq!(literal.to_string(), parsed.to_string(), "{input:?}");
+        }
+    }
+}
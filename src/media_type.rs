@@ -1,9 +1,27 @@
-use super::{error::*, media_type_buf::*, name::*, params::*, parse::*, value::*};
-use std::{
-    borrow::Cow,
-    collections::BTreeMap,
+use super::{
+    compare_options::*,
+    consts::{
+        names::{
+            self, _STAR, APPLICATION, BOUNDARY, CBOR, ECMASCRIPT, JAVASCRIPT, JSON, MULTIPART,
+            PLAIN, Q, START, START_INFO, TEXT, TYPE, XML, ZIP,
+        },
+        values::{self, US_ASCII, UTF_8},
+    },
+    error::*,
+    media_type_buf::*,
+    media_type_list::*,
+    name::*,
+    params::*,
+    parse::*,
+    parse_options::*,
+    registration_tree::*,
+    value::*,
+};
+use alloc::{borrow::Cow, string::String, vec::Vec};
+use core::{
     fmt,
     hash::{Hash, Hasher},
+    ops::Index,
 };
 
 /// A borrowed media type.
@@ -30,38 +48,134 @@ use std::{
 /// let svg = MediaType::parse("IMAGE/SVG+XML").unwrap();
 /// assert_eq!(svg, IMAGE_SVG);
 /// ```
-#[derive(Debug, Clone)]
+///
+/// # Equality and hashing
+///
+/// [`PartialEq`] and [`Hash`] agree on the same rules: names are compared
+/// case-insensitively, values by their unquoted content, and parameters as a set ordered
+/// by [`Params::sorted`]. So the parameter order does not matter, and if a name appears
+/// more than once, only the last value counts.
+///
+/// ```
+/// # use mediatype::MediaType;
+/// # use std::collections::HashSet;
+/// let a = MediaType::parse("text/plain; a=1; b=2").unwrap();
+/// let b = MediaType::parse("text/plain; b=2; a=\"1\"").unwrap();
+/// assert_eq!(a, b);
+///
+/// let set = HashSet::from([a]);
+/// assert!(set.contains(&b));
+/// ```
+///
+/// # Round-trip
+///
+/// The [`Display`](fmt::Display) output of a parsed `MediaType` can always be parsed again,
+/// and the result is equal to the original. The output is normalized: optional whitespace
+/// and trailing semicolons are removed and parameters are separated by `"; "`, while
+/// the case of names and the quoting of values are preserved.
+///
+/// ```
+/// # use mediatype::MediaType;
+/// let media_type = MediaType::parse("Text/Plain ;charset=\"UTF-8\";  ").unwrap();
+/// let displayed = media_type.to_string();
+/// assert_eq!(displayed, "Text/Plain; charset=\"UTF-8\"");
+/// assert_eq!(MediaType::parse(&displayed).unwrap(), media_type);
+/// ```
+#[derive(Clone)]
 pub struct MediaType<'a> {
     /// Top-level type.
     pub ty: Name<'a>,
 
     /// Subtype.
+    ///
+    /// The constructors and the parsers move a suffix to [`suffix`](Self::suffix).
+    /// A `+` remains in the subtype if there are several suffixes, like `vnd.foo+xml`
+    /// of `application/vnd.foo+xml+zip`, if it can not be split off, like `amr-wb+`,
+    /// if [`from_parts`](Self::from_parts) is given both a suffix and a subtype containing `+`,
+    /// or if a name containing `+` is assigned to this field directly.
+    /// Use [`try_new`](Self::try_new) to reject a subtype containing `+`.
     pub subty: Name<'a>,
 
     /// Optional suffix.
+    ///
+    /// If the subtype has more than one `+`, only the last one separates the suffix.
+    /// The preceding ones remain in [`subty`](Self::subty), so `application/vnd.foo+xml+zip`
+    /// is parsed as the subtype `vnd.foo+xml` with the suffix `zip`, and is written back as it was.
     pub suffix: Option<Name<'a>>,
 
     /// Parameters.
     pub params: Cow<'a, [(Name<'a>, Value<'a>)]>,
 }
 
+/// Owned strings of a media type: the top-level type, the subtype, the suffix and the parameters.
+///
+/// The value of a parameter without `=` ([`Value::ABSENT`]) is `None`.
+/// See [`MediaType::to_owned_parts`] and [`MediaTypeBuf::from_owned_parts`].
+pub type OwnedParts = (
+    String,
+    String,
+    Option<String>,
+    Vec<(String, Option<String>)>,
+);
+
 impl<'a> MediaType<'a> {
     /// Constructs a `MediaType` from a top-level type and a subtype.
+    ///
+    /// A subtype containing `+`, like `svg+xml`, is split into the subtype and the suffix
+    /// in the same way as [`parse`](Self::parse).
+    ///
     /// ```
-    /// # use mediatype::{names::*, MediaType};
+    /// # use mediatype::{names::*, MediaType, Name};
     /// const IMAGE_PNG: MediaType = MediaType::new(IMAGE, PNG);
     /// assert_eq!(IMAGE_PNG, MediaType::parse("image/png").unwrap());
+    ///
+    /// let svg = MediaType::new(IMAGE, Name::new("svg+xml").unwrap());
+    /// assert_eq!(svg.subty, SVG);
+    /// assert_eq!(svg.suffix, Some(XML));
     /// ```
+    ///
+    /// A subtype which can not be split, like `amr-wb+`, is kept as it is.
     #[must_use]
     pub const fn new(ty: Name<'a>, subty: Name<'a>) -> Self {
+        let (subty, suffix) = split_subtype(subty, None);
         Self {
             ty,
             subty,
-            suffix: None,
+            suffix,
             params: Cow::Borrowed(&[]),
         }
     }
 
+    /// Constructs a `MediaType` from a top-level type and a subtype, validating both of them.
+    ///
+    /// The position of an error is the byte offset in the invalid name.
+    ///
+    /// ```
+    /// # use mediatype::{names::*, MediaType, MediaTypeError};
+    /// assert_eq!(MediaType::try_new("image", "png"), Ok(MediaType::new(IMAGE, PNG)));
+    /// assert_eq!(
+    ///     MediaType::try_new("image", "png; a=b"),
+    ///     Err(MediaTypeError::InvalidSubtypeName { position: 3 })
+    /// );
+    /// ```
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if `ty` or `subty` is not a valid name,
+    /// or `subty` contains `+`.
+    pub fn try_new(ty: &'a str, subty: &'a str) -> Result<Self, MediaTypeError> {
+        if let Some(position) = invalid_name_position(ty) {
+            return Err(MediaTypeError::InvalidTypeName { position });
+        }
+        if let Some(position) = invalid_name_position(subty).or_else(|| subty.find('+')) {
+            return Err(MediaTypeError::InvalidSubtypeName { position });
+        }
+        Ok(Self::new(
+            Name::new_unchecked(ty),
+            Name::new_unchecked(subty),
+        ))
+    }
+
     /// Constructs a `MediaType` with an optional suffix and parameters.
     ///
     /// ```
@@ -72,6 +186,9 @@ impl<'a> MediaType<'a> {
     ///     MediaType::parse("image/svg+xml; charset=UTF-8").unwrap()
     /// );
     /// ```
+    ///
+    /// If `suffix` is `None`, `subty` is split like [`new`](Self::new) does.
+    /// Otherwise `subty` is kept as it is.
     #[must_use]
     pub const fn from_parts(
         ty: Name<'a>,
@@ -79,6 +196,7 @@ impl<'a> MediaType<'a> {
         suffix: Option<Name<'a>>,
         params: &'a [(Name<'a>, Value<'a>)],
     ) -> Self {
+        let (subty, suffix) = split_subtype(subty, suffix);
         Self {
             ty,
             subty,
@@ -103,27 +221,391 @@ impl<'a> MediaType<'a> {
 
     /// Constructs a `MediaType` from `str` without copying the string.
     ///
+    /// Names and values borrow from `s`. If there are no parameters, parsing does not
+    /// allocate; otherwise the `(Name, Value)` pairs are collected into a [`Vec`].
+    ///
+    /// A leading UTF-8 byte order mark is removed.
+    /// Leading and trailing whitespace (spaces and tabs) is trimmed, and empty parameters
+    /// like `text/plain; a=1 ;` are allowed, as in
+    /// [RFC 9110](https://www.rfc-editor.org/rfc/rfc9110#section-5.6.6).
+    /// Whitespace inside the essence is still rejected.
+    ///
+    /// Comments like `(comment)` of RFC 822 are not allowed in HTTP and are rejected
+    /// with [`MediaTypeError::Comment`], unless they are inside a quoted string.
+    ///
+    /// Use [`Verbatim`](crate::Verbatim) to keep the input string along with the media type.
+    ///
+    /// ```
+    /// # use mediatype::{MediaType, MediaTypeError};
+    /// assert!(MediaType::parse(" \ttext/plain; charset=UTF-8 ;").is_ok());
+    /// assert_eq!(
+    ///     MediaType::parse("text/plain garbage"),
+    ///     Err(MediaTypeError::TrailingData { position: 11 })
+    /// );
+    /// assert_eq!(
+    ///     MediaType::parse("text/plain; charset=UTF-8 (Unicode)"),
+    ///     Err(MediaTypeError::Comment { position: 26 })
+    /// );
+    /// ```
+    ///
     /// # Errors
     ///
-    /// Returns an error if the string fails to be parsed.
+    /// Returns [`MediaTypeError::TrailingData`] if unexpected characters follow
+    /// the last valid token, or another error if the string fails to be parsed.
+    ///
+    /// [`Vec`]: https://doc.rust-lang.org/std/vec/struct.Vec.html
     pub fn parse<'s: 'a>(s: &'s str) -> Result<Self, MediaTypeError> {
-        let (indices, _) = Indices::parse(s)?;
-        let params = indices
-            .params()
-            .iter()
-            .map(|param| {
-                (
-                    Name::new_unchecked(&s[param[0]..param[1]]),
-                    Value::new_unchecked(&s[param[2]..param[3]]),
-                )
-            })
-            .collect();
-        Ok(Self {
+        Self::parse_with(s, &ParseOptions::UNLIMITED)
+    }
+
+    /// Constructs a `MediaType` from `str` like [`parse`](Self::parse),
+    /// but enforces the limits of `options`.
+    ///
+    /// Unlike `parse_with`, [`parse`](Self::parse) has no limits.
+    ///
+    /// ```
+    /// # use mediatype::{MediaType, MediaTypeError, ParseOptions};
+    /// let options = ParseOptions::default();
+    /// let long = format!("text/plain; charset={}", "a".repeat(2000));
+    /// assert_eq!(
+    ///     MediaType::parse_with(&long, &options),
+    ///     Err(MediaTypeError::TooLarge { position: 1044 })
+    /// );
+    /// ```
+    ///
+    /// # Errors
+    ///
+    /// Returns [`MediaTypeError::TooLarge`] if the string exceeds a limit,
+    /// or another error if it fails to be parsed.
+    pub fn parse_with<'s: 'a>(s: &'s str, options: &ParseOptions) -> Result<Self, MediaTypeError> {
+        let (s, start) = trim_start_ows(s);
+        let mut params = Vec::new();
+        let indices = Self::parse_each(s, options, &mut params).map_err(|err| err.offset(start))?;
+        let params = if params.is_empty() {
+            Cow::Borrowed([].as_slice())
+        } else {
+            Cow::Owned(params)
+        };
+        Ok(Self::from_indices(s, &indices, params))
+    }
+
+    /// Constructs a `MediaType` from `str` like [`parse`](Self::parse),
+    /// but stores the parameters in `scratch` instead of allocating a new [`Vec`].
+    ///
+    /// `scratch` is cleared first, and the returned `MediaType` borrows it,
+    /// so `scratch` can not be used or parsed into again while the `MediaType` is alive.
+    /// Once it is dropped, the buffer can be reused without reallocation.
+    /// Modifying the parameters of the returned `MediaType` copies them into a new `Vec`.
+    ///
+    /// ```
+    /// # use mediatype::{names::*, values::*, MediaType, ReadParams};
+    /// let headers = ["text/plain; charset=UTF-8", "text/html; charset=UTF-8; q=0.9"];
+    /// let mut scratch = Vec::new();
+    /// for header in headers {
+    ///     let media_type = MediaType::parse_into(header, &mut scratch).unwrap();
+    ///     assert_eq!(media_type.get_param(CHARSET), Some(UTF_8));
+    /// }
+    /// assert!(scratch.capacity() >= 2);
+    /// ```
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the string fails to be parsed.
+    /// `scratch` may contain some of the parameters in that case.
+    ///
+    /// [`Vec`]: https://doc.rust-lang.org/std/vec/struct.Vec.html
+    pub fn parse_into<'s: 'a>(
+        s: &'s str,
+        scratch: &'a mut Vec<(Name<'s>, Value<'s>)>,
+    ) -> Result<Self, MediaTypeError> {
+        scratch.clear();
+        let (s, start) = trim_start_ows(s);
+        let indices = Self::parse_each(s, &ParseOptions::UNLIMITED, scratch)
+            .map_err(|err| err.offset(start))?;
+        let scratch: &'a [(Name<'s>, Value<'s>)] = scratch;
+        Ok(Self::from_indices(s, &indices, Cow::Borrowed(scratch)))
+    }
+
+    fn parse_each<'s>(
+        s: &'s str,
+        options: &ParseOptions,
+        params: &mut Vec<(Name<'s>, Value<'s>)>,
+    ) -> Result<Indices, MediaTypeError> {
+        let (indices, _) = Indices::parse_each(s, options, |param| {
+            params.push((
+                Name::new_unchecked(&s[param[0]..param[1]]),
+                Value::from_param(s, &param),
+            ));
+        })?;
+        Ok(indices)
+    }
+
+    fn from_indices<'s: 'a>(
+        s: &'s str,
+        indices: &Indices,
+        params: Cow<'a, [(Name<'a>, Value<'a>)]>,
+    ) -> Self {
+        Self {
             ty: Name::new_unchecked(&s[indices.ty()]),
             subty: Name::new_unchecked(&s[indices.subty()]),
             suffix: indices.suffix().map(|range| Name::new_unchecked(&s[range])),
-            params: Cow::Owned(params),
-        })
+            params,
+        }
+    }
+
+    /// Constructs a `MediaType` from `str` like [`parse`](Self::parse),
+    /// but tolerates common deviations from the syntax.
+    ///
+    /// In addition to what [`parse`](Self::parse) accepts, such as a missing space after `;`
+    /// and trailing semicolons, the following deviations are accepted:
+    ///
+    /// - Whitespace around `=` in parameters.
+    /// - Empty parameters, like `;;`.
+    /// - Parameters without a value, like `; flag`. The value is [`Value::ABSENT`],
+    ///   and they are written back without `=`, which only `parse_relaxed` reads back.
+    ///
+    /// Any other malformed input is still rejected. The [`Display`](fmt::Display)
+    /// output is normalized as usual.
+    ///
+    /// ```
+    /// # use mediatype::MediaType;
+    /// let media_type = MediaType::parse_relaxed(" text/html;; charset = utf-8;").unwrap();
+    /// assert_eq!(media_type.to_string(), "text/html; charset=utf-8");
+    /// assert!(MediaType::parse("text/html;; charset = utf-8;").is_err());
+    /// ```
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the string fails to be parsed.
+    pub fn parse_relaxed<'s: 'a>(s: &'s str) -> Result<Self, MediaTypeError> {
+        Self::parse_with(s, &ParseOptions::UNLIMITED.with_relaxed(true))
+    }
+
+    /// Constructs a `MediaType` from `str` like [`parse`](Self::parse),
+    /// but rejects the wildcard `*` as the type or the subtype.
+    ///
+    /// Use this for headers like `Content-Type` which must not contain media ranges,
+    /// and [`parse`](Self::parse) for `Accept`.
+    ///
+    /// ```
+    /// # use mediatype::{MediaType, MediaTypeError};
+    /// assert!(MediaType::parse_concrete("text/plain").is_ok());
+    /// assert_eq!(
+    ///     MediaType::parse_concrete("text/*"),
+    ///     Err(MediaTypeError::Wildcard { position: 5 })
+    /// );
+    /// ```
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the string fails to be parsed or has a wildcard.
+    pub fn parse_concrete<'s: 'a>(s: &'s str) -> Result<Self, MediaTypeError> {
+        let (trimmed, start) = trim_start_ows(s);
+        let media_type = Self::parse(trimmed).map_err(|err| err.offset(start))?;
+        if media_type.ty == _STAR {
+            Err(MediaTypeError::Wildcard { position: start })
+        } else if media_type.subty == _STAR {
+            Err(MediaTypeError::Wildcard {
+                position: start + media_type.ty.len() + 1,
+            })
+        } else {
+            Ok(media_type)
+        }
+    }
+
+    /// Constructs a `MediaType` from `str` like [`parse`](Self::parse),
+    /// but rejects duplicate parameter names.
+    ///
+    /// ```
+    /// # use mediatype::{MediaType, MediaTypeError};
+    /// assert!(MediaType::parse("text/plain; charset=UTF-8; CHARSET=UTF-8").is_ok());
+    /// assert_eq!(
+    ///     MediaType::parse_strict("text/plain; charset=UTF-8; CHARSET=UTF-8"),
+    ///     Err(MediaTypeError::DuplicateParam { position: 27 })
+    /// );
+    /// ```
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the string fails to be parsed or has duplicate parameter names.
+    pub fn parse_strict<'s: 'a>(s: &'s str) -> Result<Self, MediaTypeError> {
+        let (s, start) = trim_start_ows(s);
+        let (indices, _) = Indices::parse(s).map_err(|err| err.offset(start))?;
+        let mut params: Vec<(Name, Value)> = Vec::with_capacity(indices.params().len());
+        for param in indices.params() {
+            let name = Name::new_unchecked(&s[param[0]..param[1]]);
+            if params.iter().any(|prev| name == prev.0) {
+                return Err(MediaTypeError::DuplicateParam {
+                    position: start + param[0],
+                });
+            }
+            params.push((name, Value::from_param(s, param)));
+        }
+        let params = if params.is_empty() {
+            Cow::Borrowed([].as_slice())
+        } else {
+            Cow::Owned(params)
+        };
+        Ok(Self::from_indices(s, &indices, params))
+    }
+
+    /// Constructs a `MediaType` from `str` like [`parse`](Self::parse),
+    /// but rejects a suffix which is not a registered structured syntax suffix.
+    ///
+    /// See [`has_known_suffix`](Self::has_known_suffix). A media type without a suffix is accepted.
+    ///
+    /// ```
+    /// # use mediatype::{MediaType, MediaTypeError};
+    /// assert!(MediaType::parse_strict_suffix("image/svg+xml").is_ok());
+    /// assert!(MediaType::parse_strict_suffix("image/png").is_ok());
+    /// assert_eq!(
+    ///     MediaType::parse_strict_suffix("application/ld+jsonn"),
+    ///     Err(MediaTypeError::InvalidSuffix { position: 15 })
+    /// );
+    /// ```
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the string fails to be parsed or has an unknown suffix.
+    pub fn parse_strict_suffix<'s: 'a>(s: &'s str) -> Result<Self, MediaTypeError> {
+        let (trimmed, start) = trim_start_ows(s);
+        let media_type = Self::parse(trimmed).map_err(|err| err.offset(start))?;
+        if media_type.suffix.is_some() && !media_type.has_known_suffix() {
+            return Err(MediaTypeError::InvalidSuffix {
+                position: start + media_type.ty.len() + 1 + media_type.subty.len() + 1,
+            });
+        }
+        Ok(media_type)
+    }
+
+    /// Parses a comma-separated list of media types used in the HTTP `Accept` header,
+    /// and sorts them by their quality values in descending order.
+    ///
+    /// Media types with the same quality value keep their original order.
+    /// Empty elements are skipped.
+    ///
+    /// ```
+    /// # use mediatype::MediaType;
+    /// let list = MediaType::parse_accept("text/html, application/xml;q=0.9, */*;q=0.8, image/webp").unwrap();
+    /// assert_eq!(
+    ///     list,
+    ///     [
+    ///         MediaType::parse("text/html").unwrap(),
+    ///         MediaType::parse("image/webp").unwrap(),
+    ///         MediaType::parse("application/xml;q=0.9").unwrap(),
+    ///         MediaType::parse("*/*;q=0.8").unwrap(),
+    ///     ]
+    /// );
+    /// ```
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if any of the elements fails to be parsed.
+    pub fn parse_accept<'s: 'a>(s: &'s str) -> Result<Vec<Self>, MediaTypeError> {
+        let mut list = MediaTypeList::new(s);
+        let mut media_types = Vec::new();
+        while let Some(item) = list.next_str() {
+            if !item.chars().all(is_ows) {
+                media_types.push(Self::parse(item)?);
+            }
+        }
+        media_types.sort_by(|a, b| b.quality().total_cmp(&a.quality()));
+        Ok(media_types)
+    }
+
+    /// Checks whether `self` matches the media range `pattern`.
+    ///
+    /// - `*` in the top-level type or the subtype of `pattern` matches any name.
+    /// - If `pattern` has a suffix, `self` must have the same suffix.
+    /// - All parameters of `pattern` must be present in `self` with the same values.
+    ///   Extra parameters in `self` are ignored.
+    /// - The `q` parameter of `pattern` is a weight rather than a media type parameter,
+    ///   so it is ignored.
+    /// - An unquoted `*` as a parameter value of `pattern` matches any value,
+    ///   but `self` must still have the parameter. A quoted `"*"` only matches `*`.
+    /// - If `pattern` has duplicate parameters, only the last one is used
+    ///   in the same way as [`get_param`](ReadParams::get_param).
+    ///
+    /// ```
+    /// # use mediatype::MediaType;
+    /// let svg = MediaType::parse("image/svg+xml; charset=UTF-8").unwrap();
+    /// assert!(svg.matches(&MediaType::parse("*/*").unwrap()));
+    /// assert!(svg.matches(&MediaType::parse("image/*; q=0.8").unwrap()));
+    /// assert!(!svg.matches(&MediaType::parse("image/svg+xml; charset=utf-8").unwrap()));
+    /// assert!(!svg.matches(&MediaType::parse("text/*").unwrap()));
+    ///
+    /// let json = MediaType::parse("application/json; version=2").unwrap();
+    /// assert!(json.matches(&MediaType::parse("application/json; version=*").unwrap()));
+    /// assert!(!MediaType::parse("application/json").unwrap()
+    ///     .matches(&MediaType::parse("application/json; version=*").unwrap()));
+    /// ```
+    #[must_use]
+    pub fn matches(&self, pattern: &MediaType) -> bool {
+        (pattern.ty == _STAR || self.ty == pattern.ty)
+            && (pattern.subty == _STAR || self.subty == pattern.subty)
+            && (pattern.suffix.is_none() || self.suffix == pattern.suffix)
+            && pattern
+                .params()
+                .filter(|&(name, _)| name != Q)
+                .all(
+                    |(name, _)| match (self.get_param(name), pattern.get_param(name)) {
+                        (Some(_), Some(value)) if value.as_str() == "*" => true,
+                        (param, value) => param == value,
+                    },
+                )
+    }
+
+    /// Returns the specificity of the media range.
+    ///
+    /// A concrete top-level type outranks a concrete subtype, which outranks
+    /// any number of parameters. The `q` parameter is not counted.
+    /// This is the tie-breaker after the quality value in HTTP content negotiation.
+    ///
+    /// ```
+    /// # use mediatype::{MediaType, ReadParams};
+    /// let mut list = MediaType::parse_accept("*/*, text/*, text/plain; format=flowed, text/plain").unwrap();
+    /// list.sort_by(|a, b| {
+    ///     b.quality()
+    ///         .total_cmp(&a.quality())
+    ///         .then(b.specificity().cmp(&a.specificity()))
+    /// });
+    /// assert_eq!(
+    ///     list,
+    ///     [
+    ///         MediaType::parse("text/plain; format=flowed").unwrap(),
+    ///         MediaType::parse("text/plain").unwrap(),
+    ///         MediaType::parse("text/*").unwrap(),
+    ///         MediaType::parse("*/*").unwrap(),
+    ///     ]
+    /// );
+    /// ```
+    #[must_use]
+    pub fn specificity(&self) -> u32 {
+        let params = self.params().filter(|&(name, _)| name != Q).count();
+        (u32::from(self.ty != _STAR) << 17)
+            | (u32::from(self.subty != _STAR) << 16)
+            | u32::try_from(params).unwrap_or(u32::MAX).min(0xffff)
+    }
+
+    /// Returns an iterator over the parameters.
+    ///
+    /// Unlike [`ReadParams::params`], the items borrow the original string
+    /// rather than `self`.
+    ///
+    /// ```
+    /// # use mediatype::{names::*, MediaType};
+    /// let media_type = MediaType::parse("text/plain; charset=UTF-8; format=flowed").unwrap();
+    /// let names = media_type
+    ///     .params_iter()
+    ///     .filter(|(_, value)| value.as_str().contains('-'))
+    ///     .map(|(name, _)| name)
+    ///     .collect::<Vec<_>>();
+    /// assert_eq!(names, [CHARSET]);
+    /// ```
+    pub fn params_iter(
+        &self,
+    ) -> impl DoubleEndedIterator<Item = (Name<'a>, Value<'a>)> + ExactSizeIterator + '_ {
+        self.params.iter().copied()
     }
 
     /// Returns a [`MediaType`] without parameters.
@@ -139,180 +621,2188 @@ impl<'a> MediaType<'a> {
     ///
     /// [`MadiaType`]: ./struct.MediaType.html
     #[must_use]
-    pub const fn essence(&self) -> MediaType<'_> {
+    pub const fn essence(&self) -> MediaType<'a> {
         MediaType::from_parts(self.ty, self.subty, self.suffix, &[])
     }
-}
-
-impl<'a> ReadParams for MediaType<'a> {
-    fn params(&self) -> Params {
-        Params::from_slice(&self.params)
-    }
-
-    fn get_param(&self, name: Name) -> Option<Value> {
-        self.params
-            .iter()
-            .rev()
-            .find(|&&param| name == param.0)
-            .map(|&(_, value)| value)
-    }
-}
 
-impl<'a> WriteParams<'a> for MediaType<'a> {
-    fn set_param<'n: 'a, 'v: 'a>(&mut self, name: Name<'n>, value: Value<'v>) {
-        self.remove_params(name);
-        let params = self.params.to_mut();
-        params.push((name, value));
-    }
+    /// Compares two media types in `const` context.
+    ///
+    /// The top-level types, the subtypes, the suffixes and the parameter names are compared
+    /// case-insensitively, and the parameter values are compared byte by byte.
+    /// Unlike `==`, the parameters must appear in the same order,
+    /// and quoted values are not unquoted before comparison.
+    ///
+    /// Temporary `MediaType`s can not be dropped in `const` context,
+    /// so the operands of a `const` assertion should be `static`s.
+    ///
+    /// ```
+    /// # use mediatype::{names::*, values::*, MediaType};
+    /// static A: MediaType = MediaType::from_parts(TEXT, PLAIN, None, &[(CHARSET, UTF_8)]);
+    /// static B: MediaType = MediaType::from_parts(TEXT, PLAIN, None, &[(CHARSET, UTF_8)]);
+    /// static C: MediaType = MediaType::new(TEXT, PLAIN);
+    /// const _: () = assert!(A.const_eq(&B));
+    /// const _: () = assert!(!A.const_eq(&C));
+    /// ```
+    #[must_use]
+    pub const fn const_eq(&self, other: &MediaType) -> bool {
+        if !name_eq(self.ty, other.ty) || !name_eq(self.subty, other.subty) {
+            return false;
+        }
+        match (self.suffix, other.suffix) {
+            (Some(a), Some(b)) if name_eq(a, b) => (),
+            (None, None) => (),
+            _ => return false,
+        }
 
-    fn remove_params(&mut self, name: Name) {
-        let key_exists = self.params.iter().any(|&param| name == param.0);
-        if key_exists {
-            self.params.to_mut().retain(|&param| name != param.0);
+        let (a, b) = (const_params(&self.params), const_params(&other.params));
+        if a.len() != b.len() {
+            return false;
         }
+        let mut i = 0;
+        while i < a.len() {
+            if !name_eq(a[i].0, b[i].0)
+                || !bytes_eq(
+                    a[i].1.as_str().as_bytes(),
+                    b[i].1.as_str().as_bytes(),
+                    false,
+                )
+            {
+                return false;
+            }
+            i += 1;
+        }
+        true
     }
 
-    fn clear_params(&mut self) {
-        if !self.params.is_empty() {
-            self.params.to_mut().clear();
+    /// Gets the parameter value by its name in `const` context.
+    ///
+    /// The name is compared case-insensitively.
+    /// If the same name appears more than once, returns the last value.
+    ///
+    /// ```
+    /// # use mediatype::{names::*, values::*, MediaType, Value};
+    /// static TEXT_PLAIN: MediaType = MediaType::from_parts(TEXT, PLAIN, None, &[(CHARSET, UTF_8)]);
+    /// const CHARSET_OF: Option<Value> = TEXT_PLAIN.const_get_param(CHARSET);
+    /// assert_eq!(CHARSET_OF, Some(UTF_8));
+    /// ```
+    #[must_use]
+    pub const fn const_get_param(&self, name: Name) -> Option<Value<'a>> {
+        let params = const_params(&self.params);
+        let mut i = params.len();
+        while i > 0 {
+            i -= 1;
+            if name_eq(params[i].0, name) {
+                return Some(params[i].1);
+            }
         }
+        None
     }
-}
 
-impl<'a> fmt::Display for MediaType<'a> {
-    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
-        write!(f, "{}/{}", self.ty, self.subty)?;
+    /// Returns the canonical form as a [`MediaTypeBuf`].
+    ///
+    /// The top-level type, the subtype, the suffix and the parameter names are
+    /// converted to lowercase, and the parameters are sorted by their names.
+    /// If the same name appears more than once, only the last value is kept.
+    ///
+    /// Parameter values are left untouched because they can be case-sensitive in general.
+    /// This includes `charset`, so `charset=UTF-8` and `charset=utf-8` have different canonical forms.
+    ///
+    /// ```
+    /// # use mediatype::MediaType;
+    /// let media_type = MediaType::parse("IMAGE/SVG+XML; Format=Fixed; CHARSET=UTF-8").unwrap();
+    /// assert_eq!(
+    ///     media_type.to_canonical().as_str(),
+    ///     "image/svg+xml; charset=UTF-8; format=Fixed"
+    /// );
+    /// ```
+    #[must_use]
+    pub fn to_canonical(&self) -> MediaTypeBuf {
+        use core::fmt::Write;
+        let mut s = String::new();
+        write!(
+            s,
+            "{}/{}",
+            self.ty.as_str().to_ascii_lowercase(),
+            self.subty.as_str().to_ascii_lowercase()
+        )
+        .expect("`write` should not fail on a `String`");
         if let Some(suffix) = self.suffix {
-            write!(f, "+{}", suffix)?;
+            write!(s, "+{}", suffix.as_str().to_ascii_lowercase())
+                .expect("`write` should not fail on a `String`");
         }
-        for (name, value) in &*self.params {
-            write!(f, "; {}={}", name, value)?;
+        for (name, value) in self.params().sorted() {
+            write_param(&mut s, name.as_str().to_ascii_lowercase(), value)
+                .expect("`write` should not fail on a `String`");
         }
-        Ok(())
+        MediaTypeBuf::from_written(s).expect("all values should be valid")
     }
-}
 
-impl<'a> From<&'a MediaTypeBuf> for MediaType<'a> {
-    fn from(t: &'a MediaTypeBuf) -> Self {
-        t.to_ref()
+    /// Checks whether the suffix is `suffix`.
+    ///
+    /// ```
+    /// # use mediatype::{names::*, MediaType};
+    /// let media_type = MediaType::parse("application/ld+json").unwrap();
+    /// assert!(media_type.has_suffix(JSON));
+    /// assert!(!media_type.has_suffix(XML));
+    /// ```
+    #[must_use]
+    pub fn has_suffix(&self, suffix: Name) -> bool {
+        self.suffix == Some(suffix)
     }
-}
 
-impl<'a, 'b> PartialEq<MediaType<'b>> for MediaType<'a> {
-    fn eq(&self, other: &MediaType<'b>) -> bool {
-        self.ty == other.ty
-            && self.subty == other.subty
-            && self.suffix == other.suffix
-            && self.params().collect::<BTreeMap<_, _>>()
-                == other.params().collect::<BTreeMap<_, _>>()
+    /// Checks whether the subtype or the suffix is `json`.
+    ///
+    /// ```
+    /// # use mediatype::MediaType;
+    /// assert!(MediaType::parse("application/json").unwrap().is_json());
+    /// assert!(MediaType::parse("application/ld+json").unwrap().is_json());
+    /// assert!(!MediaType::parse("application/xml").unwrap().is_json());
+    /// ```
+    #[must_use]
+    pub fn is_json(&self) -> bool {
+        self.subty == JSON || self.has_suffix(JSON)
     }
-}
 
-impl<'a> Eq for MediaType<'a> {}
+    /// Checks whether the subtype or the suffix is `xml`.
+    ///
+    /// ```
+    /// # use mediatype::MediaType;
+    /// assert!(MediaType::parse("text/xml").unwrap().is_xml());
+    /// assert!(MediaType::parse("image/svg+xml").unwrap().is_xml());
+    /// assert!(!MediaType::parse("application/json").unwrap().is_xml());
+    /// ```
+    #[must_use]
+    pub fn is_xml(&self) -> bool {
+        self.subty == XML || self.has_suffix(XML)
+    }
 
-impl<'a> PartialEq<MediaTypeBuf> for MediaType<'a> {
-    fn eq(&self, other: &MediaTypeBuf) -> bool {
-        self.ty == other.ty()
-            && self.subty == other.subty()
-            && self.suffix == other.suffix()
-            && self.params().collect::<BTreeMap<_, _>>()
-                == other.params().collect::<BTreeMap<_, _>>()
+    /// Checks whether the suffix is a registered [structured syntax suffix],
+    /// like `json` or `xml`.
+    ///
+    /// Returns `false` if there is no suffix.
+    ///
+    /// ```
+    /// # use mediatype::MediaType;
+    /// assert!(MediaType::parse("application/ld+json").unwrap().has_known_suffix());
+    /// assert!(!MediaType::parse("application/ld+jsonn").unwrap().has_known_suffix());
+    /// assert!(!MediaType::parse("application/json").unwrap().has_known_suffix());
+    /// ```
+    ///
+    /// [structured syntax suffix]: https://www.iana.org/assignments/media-type-structured-suffix/media-type-structured-suffix.xhtml
+    #[must_use]
+    pub fn has_known_suffix(&self) -> bool {
+        self.suffix
+            .is_some_and(|suffix| KNOWN_SUFFIXES.contains(&suffix))
     }
-}
 
-impl<'a> PartialEq<&MediaTypeBuf> for MediaType<'a> {
-    fn eq(&self, other: &&MediaTypeBuf) -> bool {
-        self == *other
+    /// Returns `true` if the media type is textual and can be decoded as text.
+    ///
+    /// This includes the top-level type `text`, JSON and XML types including the suffixes,
+    /// and `application/javascript` and `application/ecmascript`.
+    ///
+    /// ```
+    /// # use mediatype::MediaType;
+    /// assert!(MediaType::parse("text/html").unwrap().is_text());
+    /// assert!(MediaType::parse("application/ld+json").unwrap().is_text());
+    /// assert!(MediaType::parse("image/svg+xml").unwrap().is_text());
+    /// assert!(!MediaType::parse("image/png").unwrap().is_text());
+    /// ```
+    #[must_use]
+    pub fn is_text(&self) -> bool {
+        self.ty == TEXT
+            || self.is_json()
+            || self.is_xml()
+            || (self.ty == APPLICATION && (self.subty == JAVASCRIPT || self.subty == ECMASCRIPT))
     }
-}
 
-impl<'a> Hash for MediaType<'a> {
-    fn hash<H: Hasher>(&self, state: &mut H) {
-        self.ty.hash(state);
-        self.subty.hash(state);
-        self.suffix.hash(state);
-        self.params()
-            .collect::<BTreeMap<_, _>>()
+    /// Returns the `charset` parameter value, or the default charset of the media type.
+    ///
+    /// The defaults are `UTF-8` for JSON types
+    /// ([RFC 8259](https://www.rfc-editor.org/rfc/rfc8259#section-8.1)) and `US-ASCII`
+    /// for `text/plain` ([RFC 6657](https://www.rfc-editor.org/rfc/rfc6657#section-4)).
+    /// Returns `None` for the other media types without the parameter, since their charsets
+    /// are defined by the content, such as the XML declaration or the HTML `<meta>` element.
+    ///
+    /// ```
+    /// # use mediatype::{values::*, MediaType};
+    /// let html = MediaType::parse("text/html; charset=UTF-8").unwrap();
+    /// assert_eq!(html.effective_charset(), Some(UTF_8));
+    /// assert_eq!(MediaType::parse("text/plain").unwrap().effective_charset(), Some(US_ASCII));
+    /// assert_eq!(MediaType::parse("application/json").unwrap().effective_charset(), Some(UTF_8));
+    /// assert_eq!(MediaType::parse("text/html").unwrap().effective_charset(), None);
+    /// ```
+    #[must_use]
+    pub fn effective_charset(&self) -> Option<Value<'_>> {
+        if let Some(charset) = self.charset() {
+            Some(charset)
+        } else if self.is_json() {
+            Some(UTF_8)
+        } else if self.ty == TEXT && self.subty == PLAIN && self.suffix.is_none() {
+            Some(US_ASCII)
+        } else {
+            None
+        }
+    }
+
+    /// Returns the `charset` parameter value with a common alias replaced
+    /// by its preferred name in the [IANA Character Sets] registry.
+    ///
+    /// Charsets are compared case-insensitively by their unquoted values,
+    /// so `utf8`, `"utf-8"` and `UTF-8` are all returned as [`UTF_8`](values::UTF_8).
+    /// An unknown charset is returned unchanged.
+    ///
+    /// ```
+    /// # use mediatype::{values::*, MediaType, Value};
+    /// let latin1 = MediaType::parse("text/plain; charset=latin1").unwrap();
+    /// assert_eq!(latin1.canonical_charset(), Some(ISO_8859_1));
+    /// let utf8 = MediaType::parse("text/plain; charset=\"utf8\"").unwrap();
+    /// assert_eq!(utf8.canonical_charset(), Some(UTF_8));
+    ///
+    /// let unknown = MediaType::parse("text/plain; charset=x-custom").unwrap();
+    /// assert_eq!(unknown.canonical_charset(), Some(Value::new("x-custom").unwrap()));
+    /// assert_eq!(MediaType::parse("text/plain").unwrap().canonical_charset(), None);
+    /// ```
+    ///
+    /// [IANA Character Sets]: https://www.iana.org/assignments/character-sets/character-sets.xhtml
+    #[must_use]
+    pub fn canonical_charset(&self) -> Option<Value<'_>> {
+        let charset = self.charset()?;
+        let unquoted = charset.unquoted();
+        let canonical = CHARSETS
+            .iter()
+            .find(|name| name.as_str().eq_ignore_ascii_case(&unquoted))
+            .or_else(|| {
+                CHARSET_ALIASES
+                    .iter()
+                    .find(|(alias, _)| alias.eq_ignore_ascii_case(&unquoted))
+                    .map(|(_, name)| name)
+            });
+        Some(canonical.copied().unwrap_or(charset))
+    }
+
+    /// Returns `true` if the top-level type is `multipart`.
+    ///
+    /// ```
+    /// # use mediatype::MediaType;
+    /// assert!(MediaType::parse("multipart/form-data; boundary=abc").unwrap().is_multipart());
+    /// assert!(!MediaType::parse("text/plain").unwrap().is_multipart());
+    /// ```
+    #[must_use]
+    pub fn is_multipart(&self) -> bool {
+        self.ty == MULTIPART
+    }
+
+    /// Returns the `boundary` parameter value.
+    ///
+    /// If the value is quoted, use [`Value::unquoted`] to get the actual boundary.
+    /// If there are duplicates, the last one is returned in the same way as
+    /// [`ReadParams::get_param`].
+    ///
+    /// ```
+    /// # use mediatype::MediaType;
+    /// let form = MediaType::parse("multipart/form-data; boundary=\"a b:c\"").unwrap();
+    /// assert_eq!(form.boundary().unwrap().unquoted(), "a b:c");
+    /// assert_eq!(MediaType::parse("multipart/mixed").unwrap().boundary(), None);
+    /// ```
+    #[must_use]
+    pub const fn boundary(&self) -> Option<Value<'a>> {
+        self.const_get_param(BOUNDARY)
+    }
+
+    /// Returns the `start` parameter value of `multipart/related`,
+    /// which is the content ID of the root body part.
+    /// ([RFC 2387](https://www.rfc-editor.org/rfc/rfc2387#section-3.2))
+    ///
+    /// ```
+    /// # use mediatype::MediaType;
+    /// let related = MediaType::parse("multipart/related; boundary=x; start=\"<root@example.com>\"").unwrap();
+    /// assert_eq!(related.start().unwrap().unquoted(), "<root@example.com>");
+    /// ```
+    #[must_use]
+    pub const fn start(&self) -> Option<Value<'a>> {
+        self.const_get_param(START)
+    }
+
+    /// Returns the `start-info` parameter value of `multipart/related`,
+    /// which is additional information for processing the root body part.
+    /// ([RFC 2387](https://www.rfc-editor.org/rfc/rfc2387#section-3.3))
+    ///
+    /// ```
+    /// # use mediatype::MediaType;
+    /// let related = MediaType::parse("multipart/related; boundary=x; start-info=\"-o ps\"").unwrap();
+    /// assert_eq!(related.start_info().unwrap().unquoted(), "-o ps");
+    /// ```
+    #[must_use]
+    pub const fn start_info(&self) -> Option<Value<'a>> {
+        self.const_get_param(START_INFO)
+    }
+
+    /// Returns the `type` parameter value of `multipart/related`,
+    /// which is the media type of the root body part.
+    /// ([RFC 2387](https://www.rfc-editor.org/rfc/rfc2387#section-3.1))
+    ///
+    /// The value is not parsed, since it is usually quoted.
+    ///
+    /// ```
+    /// # use mediatype::MediaType;
+    /// let related = MediaType::parse("multipart/related; boundary=x; type=\"text/html\"").unwrap();
+    /// let root_type = related.root_type().unwrap().unquoted();
+    /// assert_eq!(MediaType::parse(&root_type).unwrap().to_string(), "text/html");
+    /// ```
+    #[must_use]
+    pub const fn root_type(&self) -> Option<Value<'a>> {
+        self.const_get_param(TYPE)
+    }
+
+    /// Returns the base subtype and the suffix.
+    ///
+    /// If the suffix is not set but a subtype containing `+` is assigned to
+    /// [`subty`](Self::subty) directly, the subtype is split at the last `+`.
+    ///
+    /// ```
+    /// # use mediatype::{names::*, MediaType, Name};
+    /// let mut folded = MediaType::new(IMAGE, SVG);
+    /// folded.subty = Name::new("svg+xml").unwrap();
+    /// assert_eq!(folded.subtype_base(), (SVG, Some(XML)));
+    ///
+    /// let parsed = MediaType::parse("image/svg+xml").unwrap();
+    /// assert_eq!(parsed.subtype_base(), (SVG, Some(XML)));
+    /// ```
+    #[must_use]
+    pub fn subtype_base(&self) -> (Name<'a>, Option<Name<'a>>) {
+        match self.suffix {
+            Some(suffix) => (self.subty, Some(suffix)),
+            None => self.subty.split_suffix(),
+        }
+    }
+
+    /// Returns the subtype without the suffix.
+    ///
+    /// This is the subtype of [`subtype_base`](Self::subtype_base).
+    /// It differs from [`subty`](Self::subty) only if the suffix is not set
+    /// and the subtype still contains a `+` which can be split off,
+    /// like a name assigned to the field directly.
+    ///
+    /// ```
+    /// # use mediatype::{names::*, MediaType, Name};
+    /// let mut folded = MediaType::new(IMAGE, SVG);
+    /// folded.subty = Name::new("svg+xml").unwrap();
+    /// assert_eq!(folded.subtype_without_suffix(), SVG);
+    /// assert_eq!(MediaType::parse("image/svg+xml").unwrap().subtype_without_suffix(), SVG);
+    /// ```
+    #[must_use]
+    pub fn subtype_without_suffix(&self) -> Name<'a> {
+        self.subtype_base().0
+    }
+
+    /// Returns the base media type implied by a known structured suffix.
+    ///
+    /// The suffixes `+xml`, `+json`, `+cbor` and `+zip` are resolved to
+    /// `application/xml`, `application/json`, `application/cbor` and `application/zip`.
+    /// Returns `None` if there is no suffix or the suffix is unknown.
+    ///
+    /// ```
+    /// # use mediatype::{media_type, MediaType};
+    /// let svg = MediaType::parse("image/svg+xml; charset=UTF-8").unwrap();
+    /// assert_eq!(svg.suffix_base(), Some(media_type!(APPLICATION/XML)));
+    /// assert_eq!(MediaType::parse("application/xml").unwrap().suffix_base(), None);
+    /// ```
+    #[must_use]
+    pub fn suffix_base(&self) -> Option<MediaType<'static>> {
+        let suffix = self.suffix?;
+        [XML, JSON, CBOR, ZIP]
+            .into_iter()
+            .find(|&base| base == suffix)
+            .map(|base| MediaType::new(APPLICATION, base))
+    }
+
+    /// Removes all parameters without allocation.
+    ///
+    /// Unlike [`WriteParams::clear_params`], this never clones the borrowed parameters.
+    ///
+    /// ```
+    /// # use mediatype::MediaType;
+    /// let media_type = MediaType::parse("image/svg+xml; charset=UTF-8").unwrap();
+    /// assert_eq!(media_type.without_params().to_string(), "image/svg+xml");
+    /// ```
+    #[must_use]
+    pub fn without_params(self) -> Self {
+        Self {
+            params: Cow::Borrowed(&[]),
+            ..self
+        }
+    }
+
+    /// Sets a parameter value like [`WriteParams::set_param`], and returns `self`.
+    ///
+    /// ```
+    /// # use mediatype::{names::*, values::*, MediaType};
+    /// let media_type = MediaType::new(TEXT, PLAIN).with_param(CHARSET, UTF_8);
+    /// assert_eq!(media_type.to_string(), "text/plain; charset=UTF-8");
+    /// ```
+    #[must_use]
+    pub fn with_param<'n: 'a, 'v: 'a>(mut self, name: Name<'n>, value: Value<'v>) -> Self {
+        self.set_param(name, value);
+        self
+    }
+
+    /// Removes the parameters with the name like [`WriteParams::remove_params`],
+    /// and returns `self`.
+    ///
+    /// ```
+    /// # use mediatype::{names::*, MediaType};
+    /// let media_type = MediaType::parse("text/plain; charset=UTF-8; format=flowed").unwrap();
+    /// assert_eq!(
+    ///     media_type.without_param(CHARSET).to_string(),
+    ///     "text/plain; format=flowed"
+    /// );
+    /// ```
+    #[must_use]
+    pub fn without_param(mut self, name: Name) -> Self {
+        self.remove_params(name);
+        self
+    }
+
+    /// Sets a parameter value, keeping the position of the existing parameter.
+    ///
+    /// Unlike [`WriteParams::set_param`], the first parameter with the name is updated
+    /// in place, and the subsequent ones are removed. If there is no parameter with the name,
+    /// the new parameter is appended to the end.
+    ///
+    /// ```
+    /// # use mediatype::{names::*, values::*, MediaType, Value};
+    /// let mut media_type =
+    ///     MediaType::parse("multipart/form-data; boundary=a; charset=UTF-8").unwrap();
+    /// media_type.set_param_keep_order(BOUNDARY, Value::new("b").unwrap());
+    /// assert_eq!(
+    ///     media_type.to_string(),
+    ///     "multipart/form-data; boundary=b; charset=UTF-8"
+    /// );
+    /// ```
+    pub fn set_param_keep_order<'n: 'a, 'v: 'a>(&mut self, name: Name<'n>, value: Value<'v>) {
+        let params = self.params.to_mut();
+        match params.iter().position(|param| name == param.0) {
+            Some(index) => {
+                params[index] = (name, value);
+                let mut i = 0;
+                params.retain(|param| {
+                    i += 1;
+                    i <= index + 1 || name != param.0
+                });
+            }
+            None => params.push((name, value)),
+        }
+    }
+
+    /// Sets a parameter value in the same way as [`WriteParams::set_param`],
+    /// and returns the previous value.
+    ///
+    /// If the parameters with the name are duplicated, the last one is returned
+    /// in the same way as [`ReadParams::get_param`].
+    ///
+    /// ```
+    /// # use mediatype::{names::*, values::*, MediaType};
+    /// let mut text_plain = MediaType::parse("text/plain; charset=US-ASCII").unwrap();
+    /// assert_eq!(text_plain.replace_param(CHARSET, UTF_8), Some(US_ASCII));
+    /// assert_eq!(text_plain.replace_param(FORMAT, FLOWED), None);
+    /// assert_eq!(text_plain.to_string(), "text/plain; charset=UTF-8; format=flowed");
+    /// ```
+    pub fn replace_param<'n: 'a, 'v: 'a>(
+        &mut self,
+        name: Name<'n>,
+        value: Value<'v>,
+    ) -> Option<Value<'a>> {
+        let prev = self.const_get_param(name);
+        self.set_param(name, value);
+        prev
+    }
+
+    /// Renames all parameters with the name `old` to `new`.
+    ///
+    /// The values and the positions are kept. Does nothing if `old` is absent.
+    ///
+    /// ```
+    /// # use mediatype::{MediaType, Name};
+    /// let mut media_type = MediaType::parse("text/plain; enc=UTF-8; format=fixed").unwrap();
+    /// media_type.rename_param(Name::new("enc").unwrap(), Name::new("charset").unwrap());
+    ///
+    /// assert_eq!(
+    ///     media_type.to_string(),
+    ///     "text/plain; charset=UTF-8; format=fixed"
+    /// );
+    /// ```
+    pub fn rename_param<'n: 'a>(&mut self, old: Name, new: Name<'n>) {
+        let key_exists = self.params.iter().any(|&param| old == param.0);
+        if key_exists {
+            for param in self.params.to_mut() {
+                if old == param.0 {
+                    param.0 = new;
+                }
+            }
+        }
+    }
+
+    /// Replaces the suffix.
+    ///
+    /// ```
+    /// # use mediatype::{names::*, MediaType};
+    /// let media_type = MediaType::parse("application/ld+json").unwrap();
+    /// assert_eq!(media_type.with_suffix(Some(XML)).to_string(), "application/ld+xml");
+    /// ```
+    #[must_use]
+    pub fn with_suffix(self, suffix: Option<Name<'a>>) -> Self {
+        Self { suffix, ..self }
+    }
+
+    /// Removes the suffix.
+    ///
+    /// ```
+    /// # use mediatype::MediaType;
+    /// let media_type = MediaType::parse("application/ld+json; charset=UTF-8").unwrap();
+    /// assert_eq!(media_type.strip_suffix().to_string(), "application/ld; charset=UTF-8");
+    /// ```
+    #[must_use]
+    pub fn strip_suffix(self) -> Self {
+        self.with_suffix(None)
+    }
+
+    /// Returns `true` if the essence is registered in the [IANA Media Types] registry.
+    ///
+    /// Names are compared case-insensitively and parameters are ignored.
+    ///
+    /// ```
+    /// # use mediatype::MediaType;
+    /// assert!(MediaType::parse("application/json").unwrap().is_registered());
+    /// assert!(MediaType::parse("Image/SVG+XML").unwrap().is_registered());
+    /// assert!(!MediaType::parse("application/jsonn").unwrap().is_registered());
+    /// ```
+    ///
+    /// [IANA Media Types]: https://www.iana.org/assignments/media-types/media-types.xhtml
+    #[cfg(feature = "iana")]
+    #[cfg_attr(docsrs, doc(cfg(feature = "iana")))]
+    #[must_use]
+    pub fn is_registered(&self) -> bool {
+        crate::consts::is_registered(self.ty, self.subty, self.suffix)
+    }
+
+    /// Copies `self` into a [`MediaTypeBuf`], which does not borrow the input.
+    ///
+    /// The names and the values are copied as they are, without parsing them again.
+    ///
+    /// ```
+    /// # use mediatype::{names::*, MediaType, MediaTypeBuf, ReadParams};
+    /// let owned: MediaTypeBuf = {
+    ///     let input = String::from("image/svg+xml; charset=UTF-8");
+    ///     MediaType::parse(&input).unwrap().into_owned()
+    /// };
+    /// assert_eq!(owned.suffix(), Some(XML));
+    /// assert_eq!(owned.to_string(), "image/svg+xml; charset=UTF-8");
+    /// ```
+    #[must_use]
+    pub fn into_owned(self) -> MediaTypeBuf {
+        MediaTypeBuf::from(self)
+    }
+
+    /// Converts `self` into owned strings without a lifetime.
+    ///
+    /// The parameter values are kept as they are, including quotes.
+    /// Use [`MediaTypeBuf::from_owned_parts`] to convert them back.
+    ///
+    /// ```
+    /// # use mediatype::{MediaType, MediaTypeBuf};
+    /// let media_type = MediaType::parse("image/svg+xml; charset=UTF-8").unwrap();
+    /// let (ty, subty, suffix, params) = media_type.to_owned_parts();
+    /// assert_eq!((ty.as_str(), subty.as_str()), ("image", "svg"));
+    /// assert_eq!(suffix.as_deref(), Some("xml"));
+    /// assert_eq!(params, [("charset".to_string(), Some("UTF-8".to_string()))]);
+    /// ```
+    #[must_use]
+    pub fn to_owned_parts(&self) -> OwnedParts {
+        (
+            self.ty.as_str().into(),
+            self.subty.as_str().into(),
+            self.suffix.map(|suffix| suffix.as_str().into()),
+            self.params()
+                .map(|(name, value)| {
+                    let value = (!value.is_absent()).then(|| value.as_str().into());
+                    (name.as_str().into(), value)
+                })
+                .collect(),
+        )
+    }
+
+    /// Writes the media type into `w` in the same format as [`Display`](fmt::Display).
+    ///
+    /// Unlike [`to_string`](ToString::to_string), this does not allocate a `String`.
+    ///
+    /// ```
+    /// # use mediatype::MediaType;
+    /// let media_type = MediaType::parse("image/svg+xml; charset=UTF-8").unwrap();
+    /// let mut header = String::from("Content-Type: ");
+    /// media_type.write_to(&mut header).unwrap();
+    /// assert_eq!(header, "Content-Type: image/svg+xml; charset=UTF-8");
+    /// ```
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if `w` fails to write.
+    pub fn write_to<W: fmt::Write>(&self, w: &mut W) -> fmt::Result {
+        self.pieces().try_for_each(|piece| w.write_str(piece))
+    }
+
+    /// Writes the media type into a byte writer in the same way as [`write_to`](Self::write_to).
+    ///
+    /// ```
+    /// # use mediatype::MediaType;
+    /// let media_type = MediaType::parse("text/plain; charset=UTF-8").unwrap();
+    /// let mut buf = Vec::new();
+    /// media_type.write_to_io(&mut buf).unwrap();
+    /// assert_eq!(buf, b"text/plain; charset=UTF-8");
+    /// ```
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if `w` fails to write.
+    #[cfg(feature = "std")]
+    #[cfg_attr(docsrs, doc(cfg(feature = "std")))]
+    pub fn write_to_io<W: std::io::Write>(&self, w: &mut W) -> std::io::Result<()> {
+        self.pieces()
+            .try_for_each(|piece| w.write_all(piece.as_bytes()))
+    }
+
+    /// Returns the string pieces of the media type in the order of the output.
+    fn pieces(&self) -> impl Iterator<Item = &str> {
+        let essence = [self.ty.as_str(), "/", self.subty.as_str()];
+        let suffix = self
+            .suffix
             .into_iter()
-            .collect::<Vec<_>>()
-            .hash(state);
+            .flat_map(|suffix| ["+", suffix.as_str()]);
+        let params = self.params.iter().flat_map(|(name, value)| {
+            let eq = if value.is_absent() { "" } else { "=" };
+            ["; ", name.as_str(), eq, value.as_str()]
+        });
+        essence.into_iter().chain(suffix).chain(params)
+    }
+
+    /// Returns the registration tree of the subtype.
+    ///
+    /// ```
+    /// # use mediatype::{MediaType, RegistrationTree};
+    /// let media_type = MediaType::parse("application/prs.example").unwrap();
+    /// assert_eq!(media_type.registration_tree(), RegistrationTree::Personal);
+    /// ```
+    #[must_use]
+    pub fn registration_tree(&self) -> RegistrationTree {
+        RegistrationTree::of(self.subty)
+    }
+
+    /// Compares the top-level types, the subtypes and the suffixes, ignoring parameters.
+    ///
+    /// ```
+    /// # use mediatype::{names::*, MediaType};
+    /// const APPLICATION_JSON: MediaType = MediaType::new(APPLICATION, JSON);
+    /// let json = MediaType::parse("Application/JSON; charset=UTF-8").unwrap();
+    /// assert!(json.essence_eq(&APPLICATION_JSON));
+    /// assert!(!json.essence_eq(&MediaType::parse("application/ld+json").unwrap()));
+    /// ```
+    #[must_use]
+    pub fn essence_eq(&self, other: &MediaType) -> bool {
+        self.ty == other.ty && self.subty == other.subty && self.suffix == other.suffix
+    }
+
+    /// Compares the essences and the sets of parameters, ignoring the order of parameters.
+    ///
+    /// This is equivalent to `==`, which compares the parameters by
+    /// [`Params::sorted`] rather than in the stored order.
+    ///
+    /// ```
+    /// # use mediatype::MediaType;
+    /// let a = MediaType::parse("text/plain; a=1; b=2").unwrap();
+    /// let b = MediaType::parse("text/plain; b=2; a=1").unwrap();
+    /// assert!(a.semantic_eq(&b));
+    /// assert!(!a.semantic_eq(&MediaType::parse("text/plain; a=1").unwrap()));
+    /// ```
+    #[must_use]
+    pub fn semantic_eq(&self, other: &MediaType) -> bool {
+        self.essence_eq(other) && self.params().set_eq(other.params())
+    }
+
+    /// Compares in the same way as `==`, except the parameters with the names in `ignore`.
+    ///
+    /// ```
+    /// # use mediatype::{names::*, MediaType};
+    /// let a = MediaType::parse("multipart/form-data; boundary=abc; charset=UTF-8").unwrap();
+    /// let b = MediaType::parse("multipart/form-data; charset=UTF-8; boundary=xyz").unwrap();
+    /// assert!(a.eq_ignoring(&b, &[BOUNDARY]));
+    /// assert!(!a.eq_ignoring(&b, &[CHARSET]));
+    /// ```
+    #[must_use]
+    pub fn eq_ignoring(&self, other: &MediaType, ignore: &[Name]) -> bool {
+        let filter = |&(name, _): &(Name, Value)| !ignore.contains(&name);
+        self.essence_eq(other)
+            && self
+                .params()
+                .sorted()
+                .filter(filter)
+                .eq(other.params().sorted().filter(filter))
+    }
+
+    /// Compares with `other` according to `options`.
+    ///
+    /// The names are always compared case-insensitively.
+    /// See [`CompareOptions`] for the details.
+    ///
+    /// ```
+    /// # use mediatype::{CompareOptions, MediaType};
+    /// let a = MediaType::parse("text/plain; charset=UTF-8; format=fixed").unwrap();
+    /// let b = MediaType::parse("text/plain; format=fixed; charset=UTF-8").unwrap();
+    /// assert!(a.compare_with(&b, &CompareOptions::default()));
+    ///
+    /// let options = CompareOptions::new().with_ignore_param_order(false);
+    /// assert!(!a.compare_with(&b, &options));
+    /// ```
+    #[must_use]
+    pub fn compare_with(&self, other: &MediaType, options: &CompareOptions) -> bool {
+        if !self.essence_eq(other) {
+            return false;
+        }
+        if options.ignore_params {
+            return true;
+        }
+        let param_eq = |(a_name, a_value): (Name, Value), (b_name, b_value): (Name, Value)| {
+            a_name == b_name
+                && if options.case_insensitive_values {
+                    a_value.unquoted().eq_ignore_ascii_case(&b_value.unquoted())
+                } else {
+                    a_value == b_value
+                }
+        };
+        if options.ignore_param_order {
+            let (a, b) = (self.params().sorted(), other.params().sorted());
+            a.len() == b.len() && a.zip(b).all(|(a, b)| param_eq(a, b))
+        } else {
+            let (a, b) = (self.params(), other.params());
+            a.len() == b.len() && a.zip(b).all(|(a, b)| param_eq(a, b))
+        }
     }
 }
 
-#[cfg(test)]
-mod tests {
-    use super::*;
-    use crate::{names::*, values::*};
-    use std::collections::hash_map::DefaultHasher;
-    use std::str::FromStr;
+/// The charsets with their preferred names in [`values`].
+static CHARSETS: [Value<'static>; 32] = [
+    values::BIG5,
+    values::EUC_JP,
+    values::EUC_KR,
+    values::GB18030,
+    values::GB2312,
+    values::GBK,
+    values::ISO_2022_JP,
+    values::ISO_2022_JP_2,
+    values::ISO_2022_KR,
+    values::ISO_8859_1,
+    values::ISO_8859_2,
+    values::ISO_8859_3,
+    values::ISO_8859_4,
+    values::ISO_8859_5,
+    values::ISO_8859_6,
+    values::ISO_8859_6_E,
+    values::ISO_8859_6_I,
+    values::ISO_8859_7,
+    values::ISO_8859_8,
+    values::ISO_8859_8_E,
+    values::ISO_8859_8_I,
+    values::ISO_8859_9,
+    values::ISO_8859_10,
+    values::ISO_8859_15,
+    values::KOI8_R,
+    values::SHIFT_JIS,
+    values::US_ASCII,
+    values::UTF_16,
+    values::UTF_16BE,
+    values::UTF_16LE,
+    values::UTF_8,
+    values::WINDOWS_1252,
+];
+
+/// Common aliases of the charsets in [`CHARSETS`].
+static CHARSET_ALIASES: [(&str, Value<'static>); 28] = [
+    ("utf8", values::UTF_8),
+    ("unicode-1-1-utf-8", values::UTF_8),
+    ("ascii", values::US_ASCII),
+    ("us", values::US_ASCII),
+    ("ansi_x3.4-1968", values::US_ASCII),
+    ("iso646-us", values::US_ASCII),
+    ("cp367", values::US_ASCII),
+    ("latin1", values::ISO_8859_1),
+    ("l1", values::ISO_8859_1),
+    ("iso_8859-1", values::ISO_8859_1),
+    ("iso8859-1", values::ISO_8859_1),
+    ("cp819", values::ISO_8859_1),
+    ("latin9", values::ISO_8859_15),
+    ("latin-9", values::ISO_8859_15),
+    ("iso_8859-15", values::ISO_8859_15),
+    ("iso8859-15", values::ISO_8859_15),
+    ("utf16", values::UTF_16),
+    ("cp1252", values::WINDOWS_1252),
+    ("x-cp1252", values::WINDOWS_1252),
+    ("sjis", values::SHIFT_JIS),
+    ("shift-jis", values::SHIFT_JIS),
+    ("ms_kanji", values::SHIFT_JIS),
+    ("x-sjis", values::SHIFT_JIS),
+    ("eucjp", values::EUC_JP),
+    ("x-euc-jp", values::EUC_JP),
+    ("euckr", values::EUC_KR),
+    ("cp936", values::GBK),
+    ("koi8r", values::KOI8_R),
+];
+
+/// The registered structured syntax suffixes.
+static KNOWN_SUFFIXES: [Name<'static>; 16] = [
+    names::BER,
+    names::CBOR,
+    names::CBOR_SEQ,
+    names::DER,
+    names::FASTINFOSET,
+    names::GZIP,
+    names::JSON,
+    names::JSON_SEQ,
+    names::JWT,
+    names::SQLITE3,
+    names::TLV,
+    names::WBXML,
+    names::XML,
+    names::YAML,
+    names::ZIP,
+    names::ZSTD,
+];
+
+/// Moves the suffix in `subty` to `suffix` if `suffix` is not set,
+/// splitting `subty` at the last `+` like [`Name::split_suffix`].
+///
+/// `subty` is returned unchanged if it can not be split, like `amr-wb+`.
+pub(crate) const fn split_subtype<'a>(
+    subty: Name<'a>,
+    suffix: Option<Name<'a>>,
+) -> (Name<'a>, Option<Name<'a>>) {
+    if suffix.is_some() {
+        return (subty, suffix);
+    }
+    let bytes = subty.as_str().as_bytes();
+    let mut plus = bytes.len();
+    while plus > 0 && bytes[plus - 1] != b'+' {
+        plus -= 1;
+    }
+    // `subty` is a valid name, so both sides are valid if they are not empty
+    // and the suffix starts with a valid character.
+    if plus <= 1
+        || plus == bytes.len()
+        || !(bytes[plus].is_ascii_alphanumeric() || bytes[plus] == b'*')
+    {
+        return (subty, None);
+    }
+
+    let (base, rest) = bytes.split_at(plus - 1);
+    let (_, split) = rest.split_at(1);
+    match (core::str::from_utf8(base), core::str::from_utf8(split)) {
+        (Ok(base), Ok(split)) => (Name::new_unchecked(base), Some(Name::new_unchecked(split))),
+        _ => (subty, None),
+    }
+}
+
+const fn name_eq(a: Name, b: Name) -> bool {
+    bytes_eq(a.as_str().as_bytes(), b.as_str().as_bytes(), true)
+}
+
+const fn const_params<'a, 'b>(
+    params: &'b Cow<'a, [(Name<'a>, Value<'a>)]>,
+) -> &'b [(Name<'a>, Value<'a>)] {
+    match params {
+        Cow::Borrowed(params) => params,
+        Cow::Owned(params) => params.as_slice(),
+    }
+}
+
+impl<'a> ReadParams for MediaType<'a> {
+    fn params(&self) -> Params<'_> {
+        Params::from_slice(&self.params)
+    }
+
+    fn get_param(&self, name: Name) -> Option<Value<'_>> {
+        self.params
+            .iter()
+            .rev()
+            .find(|&&param| name == param.0)
+            .map(|&(_, value)| value)
+    }
+
+    fn has_param(&self, name: Name) -> bool {
+        self.params.iter().any(|&param| name == param.0)
+    }
+
+    fn params_len(&self) -> usize {
+        self.params.len()
+    }
+}
+
+/// Returns the value of the parameter like [`ReadParams::get_param`].
+///
+/// `MediaTypeBuf` does not implement `Index` since its values are not stored
+/// as [`Value`]s to borrow. Use [`ReadParams::get_param`] instead.
+///
+/// ```
+/// # use mediatype::{names::*, values::*, MediaType};
+/// let media_type = MediaType::parse("text/plain; charset=UTF-8").unwrap();
+/// assert_eq!(media_type[CHARSET], UTF_8);
+/// ```
+///
+/// # Panics
+///
+/// Panics if the parameter does not exist.
+impl<'a, 'n> Index<Name<'n>> for MediaType<'a> {
+    type Output = Value<'a>;
+
+    fn index(&self, name: Name<'n>) -> &Self::Output {
+        match self.params.iter().rfind(|&&param| name == param.0) {
+            Some((_, value)) => value,
+            None => panic!("no parameter named `{name}`"),
+        }
+    }
+}
+
+/// Sets the parameters in the same way as [`WriteParams::extend_params`],
+/// so existing parameters with the same name are removed.
+///
+/// ```
+/// # use mediatype::{names::*, values::*, MediaType};
+/// let mut media_type = MediaType::parse("text/plain; charset=US-ASCII").unwrap();
+/// media_type.extend([(FORMAT, FLOWED), (CHARSET, UTF_8)]);
+/// assert_eq!(media_type.to_string(), "text/plain; format=flowed; charset=UTF-8");
+/// ```
+impl<'a> Extend<(Name<'a>, Value<'a>)> for MediaType<'a> {
+    fn extend<I: IntoIterator<Item = (Name<'a>, Value<'a>)>>(&mut self, iter: I) {
+        self.extend_params(iter);
+    }
+}
+
+impl<'a> WriteParams<'a> for MediaType<'a> {
+    fn set_param<'n: 'a, 'v: 'a>(&mut self, name: Name<'n>, value: Value<'v>) {
+        self.remove_params(name);
+        let params = self.params.to_mut();
+        params.push((name, value));
+    }
+
+    fn extend_params<I: IntoIterator<Item = (Name<'a>, Value<'a>)>>(&mut self, iter: I) {
+        let new = iter.into_iter().collect::<Vec<_>>();
+        if new.is_empty() {
+            return;
+        }
+        let params = self.params.to_mut();
+        params.retain(|&param| new.iter().all(|&(name, _)| name != param.0));
+        for (i, &(name, value)) in new.iter().enumerate() {
+            if new[i + 1..].iter().all(|&(later, _)| name != later) {
+                params.push((name, value));
+            }
+        }
+    }
+
+    fn remove_params(&mut self, name: Name) {
+        let key_exists = self.params.iter().any(|&param| name == param.0);
+        if key_exists {
+            self.params.to_mut().retain(|&param| name != param.0);
+        }
+    }
+
+    fn clear_params(&mut self) {
+        if !self.params.is_empty() {
+            self.params.to_mut().clear();
+        }
+    }
+}
+
+/// Formats the names and the values as strings, hiding the internal representation.
+///
+/// ```
+/// # use mediatype::MediaType;
+/// let media_type = MediaType::parse("image/svg+xml; charset=UTF-8").unwrap();
+/// assert_eq!(
+///     format!("{:?}", media_type),
+///     r#"MediaType { ty: "image", subty: "svg", suffix: Some("xml"), params: [("charset", "UTF-8")] }"#
+/// );
+/// ```
+impl<'a> fmt::Debug for MediaType<'a> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        debug_fields(
+            f,
+            "MediaType",
+            self.ty,
+            self.subty,
+            self.suffix,
+            self.params(),
+        )
+    }
+}
+
+pub(crate) fn debug_fields(
+    f: &mut fmt::Formatter<'_>,
+    name: &str,
+    ty: Name,
+    subty: Name,
+    suffix: Option<Name>,
+    params: Params,
+) -> fmt::Result {
+    struct DebugParams<'a>(Params<'a>);
+
+    impl<'a> fmt::Debug for DebugParams<'a> {
+        fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+            f.debug_list()
+                .entries(
+                    self.0
+                        .clone()
+                        .map(|(name, value)| (name.as_str(), value.as_str())),
+                )
+                .finish()
+        }
+    }
+
+    f.debug_struct(name)
+        .field("ty", &ty.as_str())
+        .field("subty", &subty.as_str())
+        .field("suffix", &suffix.map(|suffix| suffix.as_str()))
+        .field("params", &DebugParams(params))
+        .finish()
+}
+
+/// The alternate format `{:#}` writes only the type, the subtype and the suffix.
+///
+/// ```
+/// # use mediatype::MediaType;
+/// let media_type = MediaType::parse("image/svg+xml; charset=UTF-8").unwrap();
+/// assert_eq!(format!("{:#}", media_type), "image/svg+xml");
+/// ```
+impl<'a> fmt::Display for MediaType<'a> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        if f.alternate() {
+            write!(f, "{}/{}", self.ty, self.subty)?;
+            if let Some(suffix) = self.suffix {
+                write!(f, "+{}", suffix)?;
+            }
+            return Ok(());
+        }
+        self.write_to(f)
+    }
+}
+
+impl<'a> TryFrom<&'a [u8]> for MediaType<'a> {
+    type Error = MediaTypeError;
+
+    /// Parses a media type from bytes such as a raw header value.
+    ///
+    /// ```
+    /// # use mediatype::{names::*, MediaType, MediaTypeError};
+    /// let media_type = MediaType::try_from(b"text/plain; charset=UTF-8".as_slice()).unwrap();
+    /// assert_eq!(media_type.ty, TEXT);
+    ///
+    /// assert_eq!(
+    ///     MediaType::try_from(b"text/\xff".as_slice()),
+    ///     Err(MediaTypeError::InvalidEncoding { position: 5 })
+    /// );
+    /// ```
+    fn try_from(bytes: &'a [u8]) -> Result<Self, Self::Error> {
+        let s = core::str::from_utf8(bytes).map_err(|err| MediaTypeError::InvalidEncoding {
+            position: err.valid_up_to(),
+        })?;
+        Self::parse(s)
+    }
+}
+
+impl<'a> From<&'a MediaTypeBuf> for MediaType<'a> {
+    fn from(t: &'a MediaTypeBuf) -> Self {
+        t.to_ref()
+    }
+}
+
+impl<'a, 'b> PartialEq<MediaType<'b>> for MediaType<'a> {
+    fn eq(&self, other: &MediaType<'b>) -> bool {
+        self.semantic_eq(other)
+    }
+}
+
+impl<'a> Eq for MediaType<'a> {}
+
+impl<'a> PartialEq<MediaTypeBuf> for MediaType<'a> {
+    fn eq(&self, other: &MediaTypeBuf) -> bool {
+        self.ty == other.ty()
+            && self.subty == other.subty()
+            && self.suffix == other.suffix()
+            && self.params().set_eq(other.params())
+    }
+}
+
+impl<'a> PartialEq<&MediaTypeBuf> for MediaType<'a> {
+    fn eq(&self, other: &&MediaTypeBuf) -> bool {
+        self == *other
+    }
+}
+
+impl<'a> PartialEq<MediaTypeBuf> for &MediaType<'a> {
+    fn eq(&self, other: &MediaTypeBuf) -> bool {
+        *self == other
+    }
+}
+
+impl<'a> PartialEq<str> for MediaType<'a> {
+    fn eq(&self, other: &str) -> bool {
+        MediaType::parse(other).is_ok_and(|other| *self == other)
+    }
+}
+
+impl<'a> PartialEq<&str> for MediaType<'a> {
+    fn eq(&self, other: &&str) -> bool {
+        self == *other
+    }
+}
+
+impl<'a> Hash for MediaType<'a> {
+    fn hash<H: Hasher>(&self, state: &mut H) {
+        self.ty.hash(state);
+        self.subty.hash(state);
+        self.suffix.hash(state);
+        self.params().set_hash(state);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{names::*, values::*};
+    use std::collections::hash_map::DefaultHasher;
+    use std::str::FromStr;
+
+    fn calculate_hash<T: Hash>(t: &T) -> u64 {
+        let mut s = DefaultHasher::new();
+        t.hash(&mut s);
+        s.finish()
+    }
+
+    #[test]
+    fn try_new() {
+        assert_eq!(
+            MediaType::try_new("Text", "Plain").unwrap().to_string(),
+            "Text/Plain"
+        );
+        assert_eq!(
+            MediaType::try_new("*", "*"),
+            Ok(MediaType::new(_STAR, _STAR))
+        );
+        assert_eq!(
+            MediaType::try_new("", "plain"),
+            Err(MediaTypeError::InvalidTypeName { position: 0 })
+        );
+        assert_eq!(
+            MediaType::try_new("text/plain", "plain"),
+            Err(MediaTypeError::InvalidTypeName { position: 4 })
+        );
+        assert_eq!(
+            MediaType::try_new("text", &"a".repeat(128)),
+            Err(MediaTypeError::InvalidSubtypeName { position: 127 })
+        );
+    }
+
+    #[test]
+    fn to_string() {
+        assert_eq!(MediaType::new(_STAR, _STAR).to_string(), "*/*");
+        assert_eq!(MediaType::new(TEXT, PLAIN).to_string(), "text/plain");
+        assert_eq!(
+            MediaType::from_parts(IMAGE, SVG, Some(XML), &[]).to_string(),
+            "image/svg+xml"
+        );
+        assert_eq!(
+            MediaType::from_parts(TEXT, PLAIN, None, &[(CHARSET, UTF_8)]).to_string(),
+            "text/plain; charset=UTF-8"
+        );
+        assert_eq!(
+            MediaType::from_parts(IMAGE, SVG, Some(XML), &[(CHARSET, UTF_8)]).to_string(),
+            "image/svg+xml; charset=UTF-8"
+        );
+    }
+
+    #[test]
+    fn write_to() {
+        struct Limited(usize);
+
+        impl fmt::Write for Limited {
+            fn write_str(&mut self, s: &str) -> fmt::Result {
+                self.0 = self.0.checked_sub(s.len()).ok_or(fmt::Error)?;
+                Ok(())
+            }
+        }
+
+        for input in [
+            "*/*",
+            "image/svg+xml",
+            "text/plain; charset=UTF-8; a=\"b c\"",
+        ] {
+            let media_type = MediaType::parse(input).unwrap();
+            let mut out = String::new();
+            media_type.write_to(&mut out).unwrap();
+            assert_eq!(out, media_type.to_string());
+
+            assert_eq!(media_type.write_to(&mut Limited(input.len())), Ok(()));
+            assert_eq!(
+                media_type.write_to(&mut Limited(input.len() - 1)),
+                Err(fmt::Error)
+            );
+
+            #[cfg(feature = "std")]
+            {
+                let mut buf = Vec::new();
+                media_type.write_to_io(&mut buf).unwrap();
+                assert_eq!(buf, input.as_bytes());
+
+                let mut short = [0; 2];
+                assert!(media_type.write_to_io(&mut short.as_mut_slice()).is_err());
+            }
+        }
+    }
+
+    #[test]
+    fn debug() {
+        assert_eq!(
+            format!("{:?}", MediaType::new(TEXT, PLAIN)),
+            r#"MediaType { ty: "text", subty: "plain", suffix: None, params: [] }"#
+        );
+        assert_eq!(
+            format!(
+                "{:?}",
+                MediaType::parse("text/plain; charset=\"UTF-8\"").unwrap()
+            ),
+            r#"MediaType { ty: "text", subty: "plain", suffix: None, params: [("charset", "\"UTF-8\"")] }"#
+        );
+        assert_eq!(
+            format!(
+                "{:?}",
+                MediaTypeBuf::from_str("image/svg+xml; charset=UTF-8").unwrap()
+            ),
+            r#"MediaTypeBuf { ty: "image", subty: "svg", suffix: Some("xml"), params: [("charset", "UTF-8")] }"#
+        );
+    }
+
+    #[test]
+    fn param_without_value() {
+        let flag = Name::new("flag").unwrap();
+        let media_type = MediaType::parse_relaxed("text/plain; flag; charset=UTF-8").unwrap();
+        assert_eq!(media_type.get_param(flag), Some(Value::ABSENT));
+        assert_eq!(media_type.to_string(), "text/plain; flag; charset=UTF-8");
+        assert_eq!(
+            MediaType::parse_relaxed(&media_type.to_string()).unwrap(),
+            media_type
+        );
+        assert_eq!(
+            MediaType::parse(&media_type.to_string()),
+            Err(MediaTypeError::InvalidParamName { position: 16 })
+        );
+        assert_eq!(
+            media_type.to_canonical().as_str(),
+            "text/plain; charset=UTF-8; flag"
+        );
+
+        let empty = MediaType::parse("text/plain; flag=").unwrap();
+        assert_eq!(empty.get_param(flag), Some(Value::EMPTY));
+        assert_eq!(empty.to_string(), "text/plain; flag=");
+        assert_ne!(MediaType::parse_relaxed("text/plain; flag").unwrap(), empty);
+    }
+
+    #[test]
+    fn to_string_alternate() {
+        assert_eq!(format!("{:#}", MediaType::new(TEXT, PLAIN)), "text/plain");
+        assert_eq!(
+            format!(
+                "{:#}",
+                MediaType::from_parts(IMAGE, SVG, Some(XML), &[(CHARSET, UTF_8)])
+            ),
+            "image/svg+xml"
+        );
+        assert_eq!(
+            format!(
+                "{:#}",
+                MediaTypeBuf::from_str("text/plain; charset=UTF-8").unwrap()
+            ),
+            "text/plain"
+        );
+    }
+
+    #[test]
+    fn try_from_bytes() {
+        assert_eq!(
+            MediaType::try_from(b"image/svg+xml; charset=UTF-8".as_slice()),
+            MediaType::parse("image/svg+xml; charset=UTF-8")
+        );
+        assert_eq!(
+            MediaType::try_from(b"text/plain; charset=\"\xe2\x9c\x93\"".as_slice()),
+            MediaType::parse("text/plain; charset=\"\u{2713}\"")
+        );
+        assert_eq!(
+            MediaType::try_from(b"textplain".as_slice()),
+            Err(MediaTypeError::MissingSlash { position: 9 })
+        );
+        assert_eq!(
+            MediaType::try_from(b"text/plain; charset=\"\xff\"".as_slice()),
+            Err(MediaTypeError::InvalidEncoding { position: 21 })
+        );
+    }
+
+    #[test]
+    fn get_param() {
+        assert_eq!(MediaType::new(TEXT, PLAIN).get_param(CHARSET), None);
+        assert_eq!(
+            MediaType::from_parts(TEXT, PLAIN, None, &[(CHARSET, UTF_8)]).get_param(CHARSET),
+            Some(UTF_8)
+        );
+        assert_eq!(
+            MediaType::parse("image/svg+xml; charset=UTF-8; HELLO=WORLD; HELLO=world")
+                .unwrap()
+                .get_param(Name::new("hello").unwrap()),
+            Some(Value::new("world").unwrap())
+        );
+    }
+
+    #[test]
+    fn quality() {
+        assert_eq!(MediaType::new(TEXT, PLAIN).quality(), 1.0);
+        assert_eq!(
+            MediaType::parse("text/plain; q=0.5").unwrap().quality(),
+            0.5
+        );
+        assert_eq!(MediaType::parse("text/plain; Q=0").unwrap().quality(), 0.0);
+        assert_eq!(
+            MediaType::parse("text/plain; q=\"0.3\"").unwrap().quality(),
+            0.3
+        );
+        assert_eq!(MediaType::parse("text/plain; q=2").unwrap().quality(), 1.0);
+        assert_eq!(
+            MediaType::parse("text/plain; q=abc").unwrap().quality(),
+            1.0
+        );
+    }
+
+    #[test]
+    fn parse_bom_and_tabs() {
+        let media_type = MediaType::parse("\u{feff}text/plain").unwrap();
+        assert_eq!(media_type, crate::media_type!(TEXT / PLAIN));
+
+        let media_type = MediaType::parse("text/plain;\tcharset=utf-8").unwrap();
+        assert_eq!(media_type.get_param(CHARSET).unwrap(), "utf-8");
+        assert_eq!(
+            MediaType::parse("\u{feff}\t text/plain\t;\tcharset=utf-8\t"),
+            MediaType::parse("text/plain; charset=utf-8")
+        );
+
+        assert_eq!(
+            MediaType::parse("\u{feff}\u{feff}text/plain"),
+            Err(MediaTypeError::InvalidTypeName { position: 3 })
+        );
+        assert_eq!(
+            MediaType::parse("\u{feff}te xt/plain"),
+            Err(MediaTypeError::InvalidTypeName { position: 5 })
+        );
+        assert_eq!(
+            MediaType::parse("text/plain;\tcharset =utf-8"),
+            Err(MediaTypeError::InvalidParamName { position: 19 })
+        );
+        assert_eq!(
+            MediaTypeBuf::from_str("\u{feff}text/plain").map(|media_type| media_type.to_string()),
+            Ok("text/plain".into())
+        );
+    }
+
+    #[test]
+    fn parse_borrowed_params() {
+        let media_type = MediaType::parse("text/plain").unwrap();
+        assert!(matches!(media_type.params, Cow::Borrowed(&[])));
+        let media_type = MediaType::parse("text/plain;  ").unwrap();
+        assert!(matches!(media_type.params, Cow::Borrowed(&[])));
+        let media_type = MediaType::parse("text/plain; charset=UTF-8").unwrap();
+        assert_eq!(media_type.params.len(), 1);
+    }
+
+    #[test]
+    fn parse_with() {
+        let options = ParseOptions::new()
+            .with_max_length(40)
+            .with_max_params(2)
+            .with_max_param_value_len(5);
+        assert_eq!(
+            MediaType::parse_with("text/plain; a=12345; b=\"123\"", &options),
+            MediaType::parse("text/plain; a=12345; b=123")
+        );
+        assert_eq!(
+            MediaType::parse_with("text/plain; a=1; b=2; c=3", &options),
+            Err(MediaTypeError::TooLarge { position: 22 })
+        );
+        assert_eq!(
+            MediaType::parse_with("text/plain; a=123456", &options),
+            Err(MediaTypeError::TooLarge { position: 19 })
+        );
+        assert_eq!(
+            MediaType::parse_with("text/plain; a=\"12345\"", &options),
+            Err(MediaTypeError::TooLarge { position: 19 })
+        );
+        assert_eq!(
+            MediaType::parse_with(&format!("text/plain;{}", " ".repeat(30)), &options),
+            Err(MediaTypeError::TooLarge { position: 40 })
+        );
+        assert_eq!(
+            MediaType::parse_with("text/plain; a", &options),
+            Err(MediaTypeError::InvalidParams { position: 13 })
+        );
+
+        let many = "text/plain".to_string() + &"; a=1".repeat(100);
+        assert!(MediaType::parse(&many).is_ok());
+        assert_eq!(
+            MediaType::parse_with(&many, &ParseOptions::default()),
+            Err(MediaTypeError::TooLarge { position: 332 })
+        );
+    }
+
+    #[test]
+    fn surrounding_whitespace() {
+        for input in [
+            "  text/plain ",
+            "\ttext/plain\t",
+            " \t text/plain; charset=UTF-8 \t",
+        ] {
+            let media_type = MediaType::parse(input).unwrap();
+            assert_eq!(media_type.ty, TEXT, "{input:?}");
+            assert_eq!(media_type.subty, PLAIN, "{input:?}");
+            assert_eq!(
+                media_type.to_string(),
+                input.trim_matches([' ', '\t']),
+                "{input:?}"
+            );
+            assert_eq!(MediaType::parse_strict(input), Ok(media_type.clone()));
+            assert_eq!(
+                MediaType::parse_into(input, &mut Vec::new()),
+                Ok(media_type.clone())
+            );
+        }
+        assert_eq!(
+            MediaType::parse("  text /plain"),
+            Err(MediaTypeError::InvalidTypeName { position: 6 })
+        );
+        assert_eq!(
+            MediaType::parse(" text/ plain"),
+            Err(MediaTypeError::MissingSubtype { position: 6 })
+        );
+        assert_eq!(
+            MediaType::parse(" text/plain; a=1; A=2"),
+            MediaType::parse("text/plain; a=1; A=2")
+        );
+        assert_eq!(
+            MediaType::parse_strict(" text/plain; a=1; A=2"),
+            Err(MediaTypeError::DuplicateParam { position: 18 })
+        );
+        assert_eq!(
+            MediaType::parse("\r\ntext/plain"),
+            Err(MediaTypeError::InvalidTypeName { position: 0 })
+        );
+    }
+
+    #[test]
+    fn parse_into() {
+        let mut scratch = Vec::new();
+        let media_type = MediaType::parse_into("text/plain", &mut scratch).unwrap();
+        assert_eq!(media_type, MediaType::new(TEXT, PLAIN));
+
+        let input = "image/svg+xml; charset=UTF-8; hello=\"world\"";
+        let media_type = MediaType::parse_into(input, &mut scratch).unwrap();
+        assert_eq!(media_type, MediaType::parse(input).unwrap());
+        assert_eq!(media_type.to_string(), input);
+        assert!(matches!(media_type.params, Cow::Borrowed(_)));
+        assert_eq!(scratch.len(), 2);
+
+        let ptr = scratch.as_ptr();
+        let media_type = MediaType::parse_into("text/plain; charset=UTF-8", &mut scratch).unwrap();
+        assert_eq!(media_type.get_param(CHARSET), Some(UTF_8));
+        assert_eq!(scratch.as_ptr(), ptr);
+        assert_eq!(scratch.len(), 1);
+
+        assert_eq!(
+            MediaType::parse_into("text/plain; charset", &mut scratch),
+            Err(MediaTypeError::InvalidParams { position: 19 })
+        );
+    }
+
+    #[test]
+    fn parse_relaxed() {
+        for s in [
+            "text/html;",
+            "text/html;charset=utf-8",
+            "text/html; charset = utf-8",
+            "text/html; charset= \"utf-8\" ;",
+            "  text/html ;; ; charset=utf-8 ;;",
+            "text/html;\tcharset\t=\tutf-8",
+        ] {
+            let media_type = MediaType::parse_relaxed(s).unwrap();
+            assert_eq!(media_type.ty, TEXT, "{s:?}");
+            assert_eq!(media_type.subty, HTML, "{s:?}");
+            assert!(
+                matches!(media_type.params_len(), 0 | 1),
+                "{s:?}: {media_type}"
+            );
+            assert_eq!(
+                MediaType::parse(&media_type.to_string()).unwrap(),
+                media_type,
+                "{s:?}"
+            );
+        }
+        assert_eq!(
+            MediaType::parse_relaxed(" text/html;; a = 1")
+                .unwrap()
+                .to_string(),
+            "text/html; a=1"
+        );
+
+        assert_eq!(
+            MediaType::parse_relaxed("  text"),
+            Err(MediaTypeError::MissingSlash { position: 6 })
+        );
+        assert_eq!(
+            MediaType::parse_relaxed(" text/html; charset=;;"),
+            MediaType::parse("text/html; charset=")
+        );
+
+        let media_type = MediaType::parse_relaxed(" text/plain; base64 ; binary").unwrap();
+        assert_eq!(
+            media_type.params.as_ref(),
+            [
+                (Name::new_unchecked("base64"), Value::ABSENT),
+                (Name::new_unchecked("binary"), Value::ABSENT),
+            ]
+        );
+        assert_eq!(media_type.to_string(), "text/plain; base64; binary");
+        assert_eq!(
+            MediaType::parse_relaxed(&media_type.to_string()).unwrap(),
+            media_type
+        );
+        assert_eq!(
+            MediaType::parse_relaxed("text/plain; flag; charset=utf-8")
+                .unwrap()
+                .get_param(CHARSET),
+            Some(Value::new_unchecked("utf-8"))
+        );
+        assert_eq!(
+            MediaType::parse(" text/html; charset"),
+            Err(MediaTypeError::InvalidParams { position: 19 })
+        );
+        assert_eq!(
+            MediaType::parse_relaxed("text/html; charset \"utf-8\""),
+            Err(MediaTypeError::InvalidParams { position: 18 })
+        );
+        assert_eq!(
+            MediaType::parse_relaxed("text/html; char set=utf-8"),
+            Err(MediaTypeError::InvalidParamName { position: 15 })
+        );
+        assert_eq!(
+            MediaType::parse_relaxed("text/html; charset=utf 8"),
+            Err(MediaTypeError::TrailingData { position: 23 })
+        );
+        assert_eq!(
+            MediaType::parse_relaxed("text/html, text/plain"),
+            Err(MediaTypeError::TrailingData { position: 9 })
+        );
+    }
+
+    #[test]
+    fn parse_concrete() {
+        assert_eq!(
+            MediaType::parse_concrete("image/svg+xml; charset=UTF-8"),
+            MediaType::parse("image/svg+xml; charset=UTF-8")
+        );
+        assert_eq!(
+            MediaType::parse_concrete("*/*"),
+            Err(MediaTypeError::Wildcard { position: 0 })
+        );
+        assert_eq!(
+            MediaType::parse_concrete("*/plain"),
+            Err(MediaTypeError::Wildcard { position: 0 })
+        );
+        assert_eq!(
+            MediaType::parse_concrete("text/*"),
+            Err(MediaTypeError::Wildcard { position: 5 })
+        );
+        assert_eq!(
+            MediaType::parse_concrete("application/*+json; q=0.5"),
+            Err(MediaTypeError::Wildcard { position: 12 })
+        );
+        assert_eq!(
+            MediaType::parse_concrete("text/*;"),
+            Err(MediaTypeError::Wildcard { position: 5 })
+        );
+        assert_eq!(
+            MediaType::parse_concrete("  text/*"),
+            Err(MediaTypeError::Wildcard { position: 7 })
+        );
+        assert_eq!(
+            MediaType::parse_concrete("\u{feff}text/*"),
+            Err(MediaTypeError::Wildcard { position: 8 })
+        );
+        assert_eq!(
+            MediaType::parse_concrete(" \t*/plain"),
+            Err(MediaTypeError::Wildcard { position: 2 })
+        );
+        assert_eq!(
+            MediaType::parse_concrete("  text/plain; charset"),
+            MediaType::parse("  text/plain; charset")
+        );
+        assert!(MediaType::parse("text/*").is_ok());
+        assert!(MediaType::parse_concrete("text").is_err());
+    }
+
+    #[test]
+    fn parse_strict() {
+        assert_eq!(
+            MediaType::parse_strict("text/plain; a=1; b=2"),
+            MediaType::parse("text/plain; a=1; b=2")
+        );
+        assert_eq!(
+            MediaType::parse("text/plain; a=1; a=2")
+                .unwrap()
+                .get_param(Name::new("a").unwrap()),
+            Some(Value::new("2").unwrap())
+        );
+        assert_eq!(
+            MediaType::parse_strict("text/plain; a=1; a=2"),
+            Err(MediaTypeError::DuplicateParam { position: 17 })
+        );
+        assert_eq!(
+            MediaType::parse_strict("text/plain; a=1; b=2; A=3"),
+            Err(MediaTypeError::DuplicateParam { position: 22 })
+        );
+        assert_eq!(
+            MediaType::parse_strict("text/plain; a=1; a"),
+            Err(MediaTypeError::InvalidParams { position: 18 })
+        );
+    }
+
+    #[test]
+    fn parse_strict_suffix() {
+        for input in [
+            "image/svg+xml",
+            "application/vnd.api+JSON; charset=UTF-8",
+            "application/vnd.foo+xml+zip",
+            "application/geo+json-seq",
+            "text/plain",
+        ] {
+            assert_eq!(
+                MediaType::parse_strict_suffix(input),
+                MediaType::parse(input),
+                "{input:?}"
+            );
+        }
+        assert_eq!(
+            MediaType::parse_strict_suffix(" application/vnd.zip+xm"),
+            Err(MediaTypeError::InvalidSuffix { position: 21 })
+        );
+        assert_eq!(
+            MediaType::parse_strict_suffix(" application/json+"),
+            Err(MediaTypeError::EmptySuffix { position: 18 })
+        );
+        assert!(!MediaType::parse("application/vnd.foo+xml+zipp")
+            .unwrap()
+            .has_known_suffix());
+    }
+
+    #[test]
+    fn parse_accept() {
+        assert_eq!(MediaType::parse_accept(""), Ok(vec![]));
+        assert_eq!(MediaType::parse_accept(" , ,, "), Ok(vec![]));
+        assert_eq!(
+            MediaType::parse_accept(
+                "text/html,application/xhtml+xml,application/xml;q=0.9,*/*;q=0.8"
+            ),
+            Ok(vec![
+                MediaType::parse("text/html").unwrap(),
+                MediaType::parse("application/xhtml+xml").unwrap(),
+                MediaType::parse("application/xml;q=0.9").unwrap(),
+                MediaType::parse("*/*;q=0.8").unwrap(),
+            ])
+        );
+        assert_eq!(
+            MediaType::parse_accept("*/*;q=0.1, text/plain; q=0.5,, text/html ,image/png;q=0.5"),
+            Ok(vec![
+                MediaType::parse("text/html").unwrap(),
+                MediaType::parse("text/plain; q=0.5").unwrap(),
+                MediaType::parse("image/png;q=0.5").unwrap(),
+                MediaType::parse("*/*;q=0.1").unwrap(),
+            ])
+        );
+        assert_eq!(
+            MediaType::parse_accept("text/html; message=\"a, b\", text/plain"),
+            Ok(vec![
+                MediaType::parse("text/html; message=\"a, b\"").unwrap(),
+                MediaType::parse("text/plain").unwrap(),
+            ])
+        );
+        assert_eq!(
+            MediaType::parse_accept("text/html, text"),
+            Err(MediaTypeError::MissingSlash { position: 4 })
+        );
+    }
+
+    #[test]
+    fn matches() {
+        let svg = MediaType::parse("image/svg+xml; charset=UTF-8; hello=world").unwrap();
+        assert!(svg.matches(&MediaType::parse("*/*").unwrap()));
+        assert!(svg.matches(&MediaType::parse("image/*").unwrap()));
+        assert!(svg.matches(&MediaType::parse("IMAGE/SVG").unwrap()));
+        assert!(svg.matches(&MediaType::parse("image/svg+xml").unwrap()));
+        assert!(svg.matches(&MediaType::parse("*/svg").unwrap()));
+        assert!(svg.matches(&MediaType::parse("image/svg; CHARSET=UTF-8").unwrap()));
+        assert!(svg.matches(&MediaType::parse("image/*; charset=UTF-8; q=0.5").unwrap()));
+        assert!(!svg.matches(&MediaType::parse("text/*").unwrap()));
+        assert!(!svg.matches(&MediaType::parse("image/png").unwrap()));
+        assert!(!svg.matches(&MediaType::parse("image/svg+json").unwrap()));
+        assert!(!svg.matches(&MediaType::parse("image/*; charset=utf-8").unwrap()));
+        assert!(!svg.matches(&MediaType::parse("image/*; format=fixed").unwrap()));
+        assert!(svg.matches(&MediaType::parse("image/*; charset=*").unwrap()));
+        assert!(svg.matches(&MediaType::parse("*/*; hello=*; charset=UTF-8").unwrap()));
+        assert!(!svg.matches(&MediaType::parse("image/*; format=*").unwrap()));
+        assert!(!svg.matches(&MediaType::parse("image/*; charset=\"*\"").unwrap()));
+        assert!(MediaType::parse("image/svg; charset=*")
+            .unwrap()
+            .matches(&MediaType::parse("image/*; charset=\"*\"").unwrap()));
+
+        let pattern = MediaType::parse("image/*; charset=US-ASCII; charset=UTF-8").unwrap();
+        assert_eq!(
+            pattern.params().collect::<Vec<_>>(),
+            [(CHARSET, US_ASCII), (CHARSET, UTF_8)]
+        );
+        assert_eq!(pattern.get_param(CHARSET), Some(UTF_8));
+        assert!(svg.matches(&pattern));
+
+        let text = MediaType::new(TEXT, PLAIN);
+        assert!(text.matches(&MediaType::new(_STAR, _STAR)));
+        assert!(!text.matches(&MediaType::from_parts(
+            TEXT,
+            PLAIN,
+            None,
+            &[(CHARSET, UTF_8)]
+        )));
+    }
+
+    #[test]
+    fn specificity() {
+        let specificity = |s| MediaType::parse(s).unwrap().specificity();
+        assert_eq!(specificity("*/*"), 0);
+        assert_eq!(specificity("*/*; q=0.5"), 0);
+        assert!(specificity("text/*") > specificity("*/*; charset=UTF-8; format=flowed"));
+        assert!(specificity("text/plain") > specificity("text/*; charset=UTF-8"));
+        assert!(specificity("text/plain; charset=UTF-8") > specificity("text/plain"));
+        assert!(
+            specificity("text/plain; charset=UTF-8; format=flowed")
+                > specificity("text/plain; charset=UTF-8; q=0.9")
+        );
+        assert_eq!(
+            specificity("text/plain; charset=UTF-8"),
+            specificity("TEXT/PLAIN; CHARSET=utf-8; q=1")
+        );
+    }
+
+    #[test]
+    fn has_param() {
+        assert!(!MediaType::new(TEXT, PLAIN).has_param(CHARSET));
+        assert!(MediaType::from_parts(TEXT, PLAIN, None, &[(CHARSET, UTF_8)]).has_param(CHARSET));
+        assert!(MediaType::parse("text/plain; Charset=UTF-8")
+            .unwrap()
+            .has_param(CHARSET));
+    }
+
+    #[test]
+    fn params_iter() {
+        assert_eq!(MediaType::new(TEXT, PLAIN).params_iter().next(), None);
+
+        let media_type = MediaType::parse("text/plain; charset=UTF-8; format=flowed").unwrap();
+        let mut params = Vec::new();
+        for (name, value) in media_type.params_iter() {
+            params.push((name, value));
+        }
+        assert_eq!(params, [(CHARSET, UTF_8), (FORMAT, FLOWED)]);
+        assert_eq!(
+            media_type
+                .params_iter()
+                .rev()
+                .map(|(name, _)| name)
+                .collect::<Vec<_>>(),
+            [FORMAT, CHARSET]
+        );
+    }
+
+    #[test]
+    fn param_eq() {
+        let media_type =
+            MediaType::parse("text/plain; charset=utf-8; format=\"Flowed\"; charset=US-ASCII")
+                .unwrap();
+        assert!(media_type.param_eq(CHARSET, US_ASCII));
+        assert!(!media_type.param_eq(CHARSET, UTF_8));
+        assert!(media_type.param_eq(FORMAT, Value::new("Flowed").unwrap()));
+        assert!(!media_type.param_eq(FORMAT, FLOWED));
+        assert!(media_type.param_eq_ignore_case(FORMAT, FLOWED));
+        assert!(media_type.param_eq_ignore_case(CHARSET, Value::new("\"us-ascii\"").unwrap()));
+        assert!(!media_type.param_eq_ignore_case(CHARSET, UTF_8));
+        assert!(!media_type.param_eq_ignore_case(BOUNDARY, UTF_8));
+    }
+
+    #[test]
+    fn charset() {
+        assert_eq!(MediaType::new(TEXT, PLAIN).charset(), None);
+        assert_eq!(
+            MediaType::parse("text/plain; CHARSET=UTF-8")
+                .unwrap()
+                .charset(),
+            Some(UTF_8)
+        );
+
+        let mut media_type =
+            MediaType::parse("text/plain; charset=US-ASCII; format=flowed").unwrap();
+        media_type.set_charset(UTF_8);
+        assert_eq!(media_type.charset(), Some(UTF_8));
+        assert_eq!(
+            media_type.to_string(),
+            "text/plain; format=flowed; charset=UTF-8"
+        );
+    }
+
+    #[test]
+    fn params_len() {
+        assert_eq!(MediaType::new(TEXT, PLAIN).params_len(), 0);
+        assert!(!MediaType::new(TEXT, PLAIN).has_params());
+
+        let media_type = MediaType::parse("text/plain; charset=US-ASCII; charset=UTF-8").unwrap();
+        assert_eq!(media_type.params_len(), 2);
+        assert_eq!(media_type.params().len(), 2);
+        assert!(media_type.has_params());
+
+        let mut params = media_type.params();
+        params.next();
+        assert_eq!(params.len(), 1);
+        assert_eq!(params.size_hint(), (1, Some(1)));
+        params.next();
+        assert_eq!(params.len(), 0);
+        assert_eq!(params.next(), None);
+        assert_eq!(params.len(), 0);
+    }
+
+    #[test]
+    fn set_param() {
+        let mut media_type = MediaType::from_parts(TEXT, PLAIN, None, &[(CHARSET, UTF_8)]);
+        let lower_utf8 = Value::new("utf-8").unwrap();
+        media_type.set_param(CHARSET, lower_utf8);
+        assert_eq!(media_type.to_string(), "text/plain; charset=utf-8");
+
+        let alice = Name::new("ALICE").unwrap();
+        let bob = Value::new("bob").unwrap();
+        media_type.set_param(alice, bob);
+        media_type.set_param(alice, bob);
+
+        assert_eq!(
+            media_type.to_string(),
+            "text/plain; charset=utf-8; ALICE=bob"
+        );
+    }
+
+    #[test]
+    fn set_param_keep_order() {
+        let mut media_type =
+            MediaType::parse("text/plain; charset=US-ASCII; format=fixed; CHARSET=ISO-8859-1")
+                .unwrap();
+        media_type.set_param_keep_order(CHARSET, UTF_8);
+        assert_eq!(
+            media_type.to_string(),
+            "text/plain; charset=UTF-8; format=fixed"
+        );
+        media_type.set_param_keep_order(BOUNDARY, Value::new("a").unwrap());
+        assert_eq!(
+            media_type.to_string(),
+            "text/plain; charset=UTF-8; format=fixed; boundary=a"
+        );
+        media_type.set_param_keep_order(FORMAT, FLOWED);
+        assert_eq!(
+            media_type.to_string(),
+            "text/plain; charset=UTF-8; format=flowed; boundary=a"
+        );
+
+        let mut media_type = MediaType::new(TEXT, PLAIN);
+        media_type.set_param_keep_order(CHARSET, UTF_8);
+        assert_eq!(media_type.to_string(), "text/plain; charset=UTF-8");
+    }
+
+    #[test]
+    fn extend_params() {
+        let a = Name::new("a").unwrap();
+        let b = Name::new("b").unwrap();
+        let mut media_type = MediaType::new(TEXT, PLAIN);
+        media_type.extend_params([
+            (a, Value::new("1").unwrap()),
+            (b, Value::new("2").unwrap()),
+            (a, Value::new("3").unwrap()),
+        ]);
+        assert_eq!(media_type.to_string(), "text/plain; b=2; a=3");
+
+        let mut media_type = MediaType::parse("text/plain; A=0; charset=UTF-8").unwrap();
+        media_type.extend_params([(a, Value::new("1").unwrap())]);
+        assert_eq!(media_type.to_string(), "text/plain; charset=UTF-8; a=1");
+
+        let input = "text/plain; b=0; a=0; c=0; B=1";
+        let new = [
+            (a, Value::new("1").unwrap()),
+            (b, Value::new("2").unwrap()),
+            (a, Value::new("3").unwrap()),
+        ];
+        let mut extended = MediaType::parse(input).unwrap();
+        extended.extend_params(new);
+        let mut set = MediaType::parse(input).unwrap();
+        for (name, value) in new {
+            set.set_param(name, value);
+        }
+        assert_eq!(extended.to_string(), set.to_string());
+        assert_eq!(extended.to_string(), "text/plain; c=0; b=2; a=3");
+
+        let mut media_type = MediaType::new(TEXT, PLAIN);
+        media_type.extend_params([]);
+        assert!(matches!(media_type.params, Cow::Borrowed(_)));
+    }
+
+    #[test]
+    fn extend() {
+        let mut media_type = MediaType::new(TEXT, PLAIN);
+        media_type.extend([(CHARSET, US_ASCII), (FORMAT, FLOWED)]);
+        media_type.extend(vec![(CHARSET, UTF_8)]);
+        assert_eq!(
+            media_type.to_string(),
+            "text/plain; format=flowed; charset=UTF-8"
+        );
+    }
+
+    #[test]
+    fn remove_params() {
+        let mut media_type = MediaType::from_parts(TEXT, PLAIN, None, &[(CHARSET, UTF_8)]);
+        media_type.remove_params(CHARSET);
+        assert_eq!(media_type.to_string(), "text/plain");
 
-    fn calculate_hash<T: Hash>(t: &T) -> u64 {
-        let mut s = DefaultHasher::new();
-        t.hash(&mut s);
-        s.finish()
+        let mut media_type =
+            MediaType::parse("image/svg+xml; hello=WORLD; charset=UTF-8; HELLO=WORLD").unwrap();
+        media_type.remove_params(Name::new("hello").unwrap());
+        assert_eq!(media_type.to_string(), "image/svg+xml; charset=UTF-8");
     }
 
     #[test]
-    fn to_string() {
-        assert_eq!(MediaType::new(_STAR, _STAR).to_string(), "*/*");
-        assert_eq!(MediaType::new(TEXT, PLAIN).to_string(), "text/plain");
+    fn eq_ignoring() {
+        let a = MediaType::parse("text/plain; charset=UTF-8; format=fixed; boundary=1").unwrap();
+        let b =
+            MediaType::parse("TEXT/PLAIN; BOUNDARY=2; Format=fixed; charset=\"UTF-8\"").unwrap();
+        assert!(a.eq_ignoring(&b, &[BOUNDARY]));
+        assert!(a.eq_ignoring(&b, &[CHARSET, BOUNDARY]));
+        assert!(!a.eq_ignoring(&b, &[]));
+        assert!(!a.eq_ignoring(&b, &[CHARSET]));
+        assert!(!a.eq_ignoring(
+            &MediaType::parse("text/html; format=fixed").unwrap(),
+            &[BOUNDARY, CHARSET]
+        ));
+        assert!(MediaType::parse("text/plain; charset=UTF-8")
+            .unwrap()
+            .eq_ignoring(&MediaType::new(TEXT, PLAIN), &[CHARSET]));
+    }
+
+    #[test]
+    fn replace_param() {
+        let mut media_type =
+            MediaType::parse("text/plain; charset=US-ASCII; format=fixed; charset=\"UTF-8\"")
+                .unwrap();
+        let prev = media_type.replace_param(CHARSET, Value::new("ISO-8859-1").unwrap());
+        assert_eq!(prev.map(|value| value.as_str()), Some("\"UTF-8\""));
         assert_eq!(
-            MediaType::from_parts(IMAGE, SVG, Some(XML), &[]).to_string(),
-            "image/svg+xml"
+            media_type.to_string(),
+            "text/plain; format=fixed; charset=ISO-8859-1"
         );
         assert_eq!(
-            MediaType::from_parts(TEXT, PLAIN, None, &[(CHARSET, UTF_8)]).to_string(),
-            "text/plain; charset=UTF-8"
+            media_type.replace_param(CHARSET, UTF_8),
+            Some(Value::new("ISO-8859-1").unwrap())
         );
+        assert_eq!(media_type.replace_param(BOUNDARY, UTF_8), None);
         assert_eq!(
-            MediaType::from_parts(IMAGE, SVG, Some(XML), &[(CHARSET, UTF_8)]).to_string(),
-            "image/svg+xml; charset=UTF-8"
+            media_type.to_string(),
+            "text/plain; format=fixed; charset=UTF-8; boundary=UTF-8"
         );
     }
 
     #[test]
-    fn get_param() {
-        assert_eq!(MediaType::new(TEXT, PLAIN).get_param(CHARSET), None);
+    fn compare_with() {
+        let parse = |s| MediaType::parse(s).unwrap();
+        let default = CompareOptions::default();
+        let ordered = default.with_ignore_param_order(false);
+        let no_params = default.with_ignore_params(true);
+        let no_case = default.with_case_insensitive_values(true);
+        for (a, b) in [
+            ("text/plain", "TEXT/Plain"),
+            ("text/plain; a=1; b=2", "text/plain; b=2; a=1"),
+            ("text/plain; a=1; a=2", "text/plain; a=2"),
+            ("text/plain; a=x", "text/plain; a=X"),
+            ("text/plain; a=x", "image/png"),
+            ("text/plain; a=\"x\"", "text/plain; A=x"),
+            ("text/plain", "text/plain; a=1"),
+        ] {
+            let (a, b) = (parse(a), parse(b));
+            assert_eq!(a.compare_with(&b, &default), a == b, "{a} {b}");
+            assert_eq!(a.compare_with(&b, &no_params), a.essence_eq(&b), "{a} {b}");
+        }
+
+        let (a, b) = (parse("text/plain; a=1; b=2"), parse("text/plain; b=2; a=1"));
+        assert!(!a.compare_with(&b, &ordered));
+        assert!(a.compare_with(&a.clone(), &ordered));
+        let (a, b) = (parse("text/plain; a=1; a=2"), parse("text/plain; a=2"));
+        assert!(!a.compare_with(&b, &ordered));
+
+        let (a, b) = (parse("text/plain; a=\"Xy\""), parse("text/plain; a=xY"));
+        assert!(a.compare_with(&b, &no_case));
+        assert!(!a.compare_with(&b, &default));
+        assert!(a.compare_with(&b, &no_case.with_ignore_param_order(false)));
+    }
+
+    #[test]
+    fn rename_param() {
+        let mut media_type =
+            MediaType::parse("text/plain; enc=UTF-8; format=fixed; ENC=US-ASCII").unwrap();
+        media_type.rename_param(Name::new("enc").unwrap(), CHARSET);
         assert_eq!(
-            MediaType::from_parts(TEXT, PLAIN, None, &[(CHARSET, UTF_8)]).get_param(CHARSET),
-            Some(UTF_8)
+            media_type.to_string(),
+            "text/plain; charset=UTF-8; format=fixed; charset=US-ASCII"
         );
+        assert_eq!(media_type.get_param(CHARSET), Some(US_ASCII));
+
+        let mut media_type = MediaType::from_parts(TEXT, PLAIN, None, &[(CHARSET, UTF_8)]);
+        media_type.rename_param(FORMAT, BOUNDARY);
+        assert!(matches!(media_type.params, Cow::Borrowed(_)));
+        assert_eq!(media_type.to_string(), "text/plain; charset=UTF-8");
+    }
+
+    #[test]
+    fn without_params() {
+        let media_type =
+            MediaType::from_parts(TEXT, PLAIN, None, &[(CHARSET, UTF_8)]).without_params();
+        assert!(matches!(media_type.params, Cow::Borrowed(&[])));
+        assert_eq!(media_type, MediaType::new(TEXT, PLAIN));
+
+        let media_type = MediaType::parse("image/svg+xml; charset=UTF-8")
+            .unwrap()
+            .without_params();
+        assert!(matches!(media_type.params, Cow::Borrowed(&[])));
+        assert_eq!(media_type.to_string(), "image/svg+xml");
+    }
+
+    #[test]
+    fn is_text() {
+        for s in [
+            "text/plain",
+            "TEXT/CSV; charset=UTF-8",
+            "application/json",
+            "application/problem+json",
+            "application/xml",
+            "image/svg+xml",
+            "application/javascript",
+            "Application/ECMAScript",
+        ] {
+            assert!(MediaType::parse(s).unwrap().is_text(), "{s:?}");
+        }
+        for s in [
+            "image/png",
+            "application/octet-stream",
+            "font/javascript",
+            "multipart/form-data; charset=UTF-8",
+        ] {
+            assert!(!MediaType::parse(s).unwrap().is_text(), "{s:?}");
+        }
+    }
+
+    #[test]
+    fn effective_charset() {
+        for (s, charset) in [
+            ("text/plain", Some(US_ASCII)),
+            ("Text/Plain; charset=\"UTF-8\"", Some(UTF_8)),
+            ("application/json", Some(UTF_8)),
+            ("application/ld+json; charset=US-ASCII", Some(US_ASCII)),
+            ("text/plain+xml", None),
+            ("text/html", None),
+            ("application/xml", None),
+            ("image/png", None),
+        ] {
+            assert_eq!(
+                MediaType::parse(s).unwrap().effective_charset(),
+                charset,
+                "{s:?}"
+            );
+        }
+    }
+
+    #[test]
+    fn index() {
+        let media_type =
+            MediaType::parse("text/plain; charset=UTF-8; CHARSET=\"US-ASCII\"").unwrap();
+        assert_eq!(media_type[CHARSET], "US-ASCII");
+        assert_eq!(media_type[CHARSET].as_str(), "\"US-ASCII\"");
         assert_eq!(
-            MediaType::parse("image/svg+xml; charset=UTF-8; HELLO=WORLD; HELLO=world")
-                .unwrap()
-                .get_param(Name::new("hello").unwrap()),
-            Some(Value::new("world").unwrap())
+            media_type[Name::new("charset").unwrap()],
+            media_type.get_param(CHARSET).unwrap()
         );
     }
 
     #[test]
-    fn set_param() {
-        let mut media_type = MediaType::from_parts(TEXT, PLAIN, None, &[(CHARSET, UTF_8)]);
-        let lower_utf8 = Value::new("utf-8").unwrap();
-        media_type.set_param(CHARSET, lower_utf8);
-        assert_eq!(media_type.to_string(), "text/plain; charset=utf-8");
-
-        let alice = Name::new("ALICE").unwrap();
-        let bob = Value::new("bob").unwrap();
-        media_type.set_param(alice, bob);
-        media_type.set_param(alice, bob);
+    #[should_panic(expected = "no parameter named `boundary`")]
+    fn index_missing() {
+        let media_type = MediaType::parse("text/plain; charset=UTF-8").unwrap();
+        let _ = media_type[BOUNDARY];
+    }
 
+    #[test]
+    fn with_param() {
+        let media_type = MediaType::new(TEXT, PLAIN)
+            .with_param(CHARSET, UTF_8)
+            .with_param(FORMAT, Value::new("flowed").unwrap())
+            .with_param(CHARSET, Value::new("US-ASCII").unwrap());
         assert_eq!(
             media_type.to_string(),
-            "text/plain; charset=utf-8; ALICE=bob"
+            "text/plain; format=flowed; charset=US-ASCII"
+        );
+        assert_eq!(
+            media_type.clone().without_param(FORMAT).to_string(),
+            "text/plain; charset=US-ASCII"
+        );
+        assert!(matches!(
+            MediaType::new(TEXT, PLAIN).without_param(CHARSET).params,
+            Cow::Borrowed(&[])
+        ));
+        assert_eq!(
+            media_type.without_param(CHARSET).without_param(FORMAT),
+            MediaType::new(TEXT, PLAIN)
         );
     }
 
     #[test]
-    fn remove_params() {
-        let mut media_type = MediaType::from_parts(TEXT, PLAIN, None, &[(CHARSET, UTF_8)]);
-        media_type.remove_params(CHARSET);
-        assert_eq!(media_type.to_string(), "text/plain");
+    fn strip_suffix() {
+        let media_type = MediaType::parse("image/svg+xml; charset=UTF-8").unwrap();
+        assert_eq!(
+            media_type.clone().strip_suffix(),
+            MediaType::parse("image/svg; charset=UTF-8").unwrap()
+        );
+        assert_eq!(
+            media_type.with_suffix(Some(JSON)).to_string(),
+            "image/svg+json; charset=UTF-8"
+        );
 
-        let mut media_type =
-            MediaType::parse("image/svg+xml; hello=WORLD; charset=UTF-8; HELLO=WORLD").unwrap();
-        media_type.remove_params(Name::new("hello").unwrap());
-        assert_eq!(media_type.to_string(), "image/svg+xml; charset=UTF-8");
+        let media_type = MediaType::parse("text/plain; charset=UTF-8").unwrap();
+        assert_eq!(media_type.clone().strip_suffix(), media_type);
+        assert_eq!(
+            media_type.clone().with_suffix(None).to_string(),
+            media_type.to_string()
+        );
     }
 
     #[test]
@@ -346,11 +2836,505 @@ mod tests {
             MediaTypeBuf::from_str("image/svg+xml; charset=UTF-8").unwrap(),
         );
 
+        assert_ne!(
+            MediaType::parse("text/plain; charset=UTF-8").unwrap(),
+            MediaType::parse("text/plain; charset=utf-8").unwrap()
+        );
+        assert_eq!(
+            MediaType::parse("text/plain; charset=UTF-8").unwrap(),
+            MediaType::parse("text/plain; charset=\"UTF-8\"").unwrap()
+        );
+
         const TEXT_PLAIN: MediaType = MediaType::from_parts(TEXT, PLAIN, None, &[]);
         let text_plain = MediaType::parse("text/plain").unwrap();
         assert_eq!(text_plain.essence(), TEXT_PLAIN);
     }
 
+    #[test]
+    fn semantic_eq() {
+        let a = MediaType::parse("text/plain; a=1; b=2").unwrap();
+        let b = MediaType::parse("text/plain; B=2; A=1").unwrap();
+        assert!(a.semantic_eq(&b));
+        assert!(a.semantic_eq(&MediaType::parse("text/plain; a=0; b=2; a=1").unwrap()));
+        assert!(!a.semantic_eq(&MediaType::parse("text/plain; a=1; b=3").unwrap()));
+        assert!(!a.semantic_eq(&MediaType::parse("text/plain; a=1; b=2; c=3").unwrap()));
+        assert!(!a.semantic_eq(&MediaType::parse("text/html; a=1; b=2").unwrap()));
+        assert_eq!(
+            a.params().sorted().collect::<Vec<_>>(),
+            b.params().sorted().collect::<Vec<_>>()
+        );
+        assert_eq!(
+            b.params()
+                .sorted()
+                .rev()
+                .map(|(name, _)| name)
+                .collect::<Vec<_>>(),
+            [Name::new("B").unwrap(), Name::new("A").unwrap()]
+        );
+    }
+
+    #[cfg(feature = "iana")]
+    #[test]
+    fn is_registered() {
+        let registered = |s| MediaType::parse(s).unwrap().is_registered();
+        assert!(registered("application/json"));
+        assert!(registered("application/ld+json; charset=UTF-8"));
+        assert!(registered("TEXT/PLAIN"));
+        assert!(registered("application/vnd.ms-excel"));
+        assert!(registered("video/vp9"));
+        assert!(registered("application/1d-interleaved-parityfec"));
+        assert!(!registered("application/jsonn"));
+        assert!(!registered("application/ld"));
+        assert!(!registered("application/x-tar"));
+        assert!(!registered("*/*"));
+    }
+
+    #[test]
+    fn into_owned() {
+        for s in [
+            "text/plain",
+            "image/svg+xml; charset=UTF-8",
+            "Multipart/Form-Data; boundary=\"a b\"; charset=US-ASCII; charset=UTF-8",
+            "application/vnd.foo+xml+zip;a=\"\\\"\";b=",
+        ] {
+            let media_type = MediaType::parse(s).unwrap();
+            let expected = MediaTypeBuf::from_string(media_type.to_string()).unwrap();
+            let buf = media_type.clone().into_owned();
+            assert_eq!(buf.as_str(), expected.as_str());
+            assert_eq!(buf.ty(), media_type.ty);
+            assert_eq!(buf.subty().as_str(), media_type.subty.as_str());
+            assert_eq!(buf.suffix(), media_type.suffix);
+            assert!(buf
+                .params()
+                .map(|(name, value)| (name.as_str(), value.as_str()))
+                .eq(media_type
+                    .params()
+                    .map(|(name, value)| (name.as_str(), value.as_str()))));
+            assert_eq!(buf.get_param(CHARSET), media_type.get_param(CHARSET));
+        }
+
+        let built = MediaType::from_parts(IMAGE, SVG, Some(XML), &[(CHARSET, UTF_8)]);
+        assert_eq!(
+            built.into_owned().to_string(),
+            "image/svg+xml; charset=UTF-8"
+        );
+
+        let mut folded = MediaType::new(IMAGE, SVG);
+        folded.subty = Name::new("svg+xml").unwrap();
+        let expected = MediaTypeBuf::from_str(&folded.to_string()).unwrap();
+        let buf = folded.clone().into_owned();
+        assert_eq!(buf, expected);
+        assert_eq!((buf.subty(), buf.suffix()), (SVG, Some(XML)));
+        assert_eq!(MediaTypeBuf::from(&folded).as_str(), "image/svg+xml");
+    }
+
+    #[test]
+    fn owned_parts() {
+        for s in [
+            "text/plain",
+            "image/svg+xml; charset=UTF-8",
+            "multipart/form-data; boundary=\"a b\"; charset=US-ASCII",
+        ] {
+            let media_type = MediaType::parse(s).unwrap();
+            let buf = MediaTypeBuf::from_owned_parts(media_type.to_owned_parts()).unwrap();
+            assert_eq!(buf, media_type);
+            assert_eq!(buf.as_str(), s);
+        }
+
+        let media_type = MediaType::parse_relaxed("text/plain; flag").unwrap();
+        let parts = media_type.to_owned_parts();
+        assert_eq!(parts.3, [("flag".to_string(), None)]);
+        let buf = MediaTypeBuf::from_owned_parts(parts).unwrap();
+        assert_eq!(buf, media_type);
+        assert_eq!(buf.as_str(), "text/plain; flag");
+
+        let parts = |ty: &str, subty: &str, suffix: Option<&str>, params: &[(&str, &str)]| {
+            MediaTypeBuf::from_owned_parts((
+                ty.to_string(),
+                subty.to_string(),
+                suffix.map(str::to_string),
+                params
+                    .iter()
+                    .map(|(name, value)| (name.to_string(), Some(value.to_string())))
+                    .collect(),
+            ))
+        };
+        assert_eq!(
+            parts("text/plain", "plain", None, &[]),
+            Err(MediaTypeError::InvalidTypeName { position: 4 })
+        );
+        assert_eq!(
+            parts("text", "", None, &[]),
+            Err(MediaTypeError::InvalidSubtypeName { position: 0 })
+        );
+        assert_eq!(
+            parts("image", "svg", Some("+xml"), &[]),
+            Err(MediaTypeError::InvalidSuffix { position: 0 })
+        );
+        assert_eq!(
+            parts("text", "plain", None, &[("char set", "UTF-8")]),
+            Err(MediaTypeError::InvalidParamName { position: 4 })
+        );
+        assert_eq!(
+            parts("text", "plain", None, &[("charset", "UTF-8; a=b")]),
+            Err(MediaTypeError::InvalidParamValue { position: 5 })
+        );
+        assert_eq!(
+            parts("text", "plain", None, &[("charset", "\"UTF-8")]),
+            Err(MediaTypeError::InvalidParamValue { position: 6 })
+        );
+        assert_eq!(
+            parts("text", "plain", None, &[("charset", "\"UTF-8\"x")]),
+            Err(MediaTypeError::InvalidParamValue { position: 7 })
+        );
+    }
+
+    #[test]
+    fn essence_eq() {
+        let svg = MediaType::parse("image/svg+xml; charset=UTF-8").unwrap();
+        assert!(svg.essence_eq(&MediaType::parse("IMAGE/SVG+XML").unwrap()));
+        assert!(svg.essence_eq(&MediaType::parse("image/svg+xml; charset=US-ASCII").unwrap()));
+        assert!(svg.essence_eq(&svg.essence()));
+        assert!(!svg.essence_eq(&MediaType::parse("image/svg").unwrap()));
+        assert!(!svg.essence_eq(&MediaType::parse("image/png+xml").unwrap()));
+        assert!(!svg.essence_eq(&MediaType::parse("text/svg+xml").unwrap()));
+    }
+
+    #[test]
+    fn to_canonical() {
+        assert_eq!(
+            MediaType::parse("TEXT/PLAIN")
+                .unwrap()
+                .to_canonical()
+                .as_str(),
+            "text/plain"
+        );
+        assert_eq!(
+            MediaType::parse("Image/SVG+XML; b=2; A=1; charset=\"UTF-8\"")
+                .unwrap()
+                .to_canonical()
+                .as_str(),
+            "image/svg+xml; a=1; b=2; charset=\"UTF-8\""
+        );
+        assert_eq!(
+            MediaType::parse("text/plain; charset=US-ASCII; format=flowed; CHARSET=utf-8")
+                .unwrap()
+                .to_canonical()
+                .as_str(),
+            "text/plain; charset=utf-8; format=flowed"
+        );
+        assert_eq!(
+            MediaType::parse("text/plain; b=2; a=1")
+                .unwrap()
+                .to_canonical(),
+            MediaType::parse("text/plain; a=1; b=2")
+                .unwrap()
+                .to_canonical()
+        );
+    }
+
+    #[test]
+    fn suffix() {
+        assert!(MediaType::parse("image/svg+XML").unwrap().has_suffix(XML));
+        assert!(!MediaType::parse("application/xml").unwrap().has_suffix(XML));
+        assert!(MediaType::parse("APPLICATION/JSON").unwrap().is_json());
+        assert!(MediaType::parse("application/vnd.api+json; charset=UTF-8")
+            .unwrap()
+            .is_json());
+        assert!(!MediaType::parse("application/json-patch+xml")
+            .unwrap()
+            .is_json());
+        assert!(MediaType::parse("application/json-patch+xml")
+            .unwrap()
+            .is_xml());
+        assert!(MediaType::parse("application/XML").unwrap().is_xml());
+        assert!(!MediaType::parse("text/plain").unwrap().is_xml());
+    }
+
+    #[test]
+    fn canonical_charset() {
+        let charset = |s| {
+            MediaType::parse(s)
+                .unwrap()
+                .canonical_charset()
+                .map(|value| value.to_string())
+        };
+        assert_eq!(
+            charset("text/plain; charset=UTF-8").as_deref(),
+            Some("UTF-8")
+        );
+        assert_eq!(
+            charset("text/plain; charset=utf-8").as_deref(),
+            Some("UTF-8")
+        );
+        assert_eq!(
+            charset("text/plain; charset=UTF8").as_deref(),
+            Some("UTF-8")
+        );
+        assert_eq!(
+            charset("text/plain; CHARSET=\"Latin1\"").as_deref(),
+            Some("ISO-8859-1")
+        );
+        assert_eq!(
+            charset("text/plain; charset=iso-8859-1").as_deref(),
+            Some("ISO-8859-1")
+        );
+        assert_eq!(
+            charset("text/plain; charset=shift_jis").as_deref(),
+            Some("Shift_JIS")
+        );
+        assert_eq!(
+            charset("text/plain; charset=CP1252").as_deref(),
+            Some("windows-1252")
+        );
+        assert_eq!(
+            charset("text/plain; charset=\"x-Unknown\"").as_deref(),
+            Some("\"x-Unknown\"")
+        );
+        assert_eq!(charset("text/plain; charset=\"\"").as_deref(), Some("\"\""));
+        assert_eq!(charset("text/plain"), None);
+
+        for name in &CHARSETS {
+            assert!(!CHARSET_ALIASES
+                .iter()
+                .any(|(alias, _)| name.as_str().eq_ignore_ascii_case(alias)));
+        }
+    }
+
+    #[test]
+    fn multipart() {
+        let form = MediaType::parse("Multipart/Form-Data; BOUNDARY=\"--a\\\"b\"").unwrap();
+        assert!(form.is_multipart());
+        assert_eq!(form.boundary().unwrap().as_str(), "\"--a\\\"b\"");
+        assert_eq!(form.boundary().unwrap().unquoted(), "--a\"b");
+
+        let plain = MediaType::parse("text/plain; boundary=abc").unwrap();
+        assert!(!plain.is_multipart());
+        assert_eq!(plain.boundary(), Some(Value::new("abc").unwrap()));
+
+        let related = MediaType::parse(
+            "multipart/related; boundary=x; Type=\"application/xop+xml\"; START=\"<a@b>\"; start-info=\"text/xml\"; start=\"<c@d>\"",
+        )
+        .unwrap();
+        assert_eq!(
+            related.root_type().unwrap().unquoted(),
+            "application/xop+xml"
+        );
+        assert_eq!(related.start().unwrap().unquoted(), "<c@d>");
+        assert_eq!(related.start_info().unwrap().unquoted(), "text/xml");
+        assert_eq!(related.boundary(), Some(Value::new("x").unwrap()));
+
+        const MULTIPART_RELATED: MediaType = MediaType::new(MULTIPART, RELATED);
+        assert_eq!(MULTIPART_RELATED.start(), None);
+        assert_eq!(MULTIPART_RELATED.start_info(), None);
+        assert_eq!(MULTIPART_RELATED.root_type(), None);
+    }
+
+    #[test]
+    fn suffix_base() {
+        let base = |s| MediaType::parse(s).unwrap().suffix_base();
+        assert_eq!(
+            base("image/svg+xml"),
+            Some(MediaType::new(APPLICATION, XML))
+        );
+        assert_eq!(
+            base("application/ld+JSON"),
+            Some(MediaType::new(APPLICATION, JSON))
+        );
+        assert_eq!(
+            base("application/senml+cbor"),
+            Some(MediaType::new(APPLICATION, CBOR))
+        );
+        assert_eq!(
+            base("application/epub+zip"),
+            Some(MediaType::new(APPLICATION, ZIP))
+        );
+        assert_eq!(
+            base("application/vnd.foo+xml+zip"),
+            Some(MediaType::new(APPLICATION, ZIP))
+        );
+        assert_eq!(base("application/foo+yaml"), None);
+        assert_eq!(base("application/json"), None);
+    }
+
+    #[test]
+    fn subtype_without_suffix() {
+        for (s, subty) in [
+            ("image/svg", "svg"),
+            ("image/svg+xml", "svg"),
+            ("application/vnd.foo+xml+zip", "vnd.foo+xml"),
+        ] {
+            let media_type = MediaType::parse(s).unwrap();
+            assert_eq!(media_type.subty, subty, "{s:?}");
+            assert_eq!(media_type.subtype_without_suffix(), subty, "{s:?}");
+
+            let mut folded = media_type.clone();
+            folded.subty = Name::new(&s[s.find('/').unwrap() + 1..]).unwrap();
+            folded.suffix = None;
+            assert_eq!(folded.subtype_without_suffix(), subty, "{s:?}");
+        }
+    }
+
+    #[test]
+    fn subtype_without_plus() {
+        let svg_xml = Name::new("svg+xml").unwrap();
+        for media_type in [
+            MediaType::parse("image/svg+xml").unwrap(),
+            MediaType::new(IMAGE, svg_xml),
+            MediaType::from_parts(IMAGE, svg_xml, None, &[(CHARSET, UTF_8)]),
+            MediaType::from_parts(IMAGE, SVG, Some(XML), &[(CHARSET, UTF_8)]),
+            crate::MediaTypeBuilder::new()
+                .ty(IMAGE)
+                .subty(svg_xml)
+                .build(),
+            crate::media_type!("image/svg+xml"),
+        ] {
+            assert!(!media_type.subty.as_str().contains('+'), "{media_type}");
+            assert_eq!(media_type.subty, SVG, "{media_type}");
+            assert_eq!(media_type.suffix, Some(XML), "{media_type}");
+            assert_eq!(media_type.subtype_without_suffix(), media_type.subty);
+        }
+        let buf = MediaTypeBuf::new(IMAGE, svg_xml);
+        assert_eq!((buf.subty(), buf.suffix()), (SVG, Some(XML)));
+
+        assert_eq!(
+            MediaType::try_new("image", "svg+xml"),
+            Err(MediaTypeError::InvalidSubtypeName { position: 3 })
+        );
+
+        let vnd = Name::new("vnd.foo+xml+zip").unwrap();
+        let parsed = MediaType::parse("application/vnd.foo+xml+zip").unwrap();
+        assert_eq!(
+            (parsed.subty.as_str(), parsed.suffix),
+            ("vnd.foo+xml", Some(ZIP))
+        );
+        let media_type = MediaType::new(APPLICATION, vnd);
+        assert_eq!(
+            (media_type.subty, media_type.suffix),
+            (parsed.subty, parsed.suffix)
+        );
+        assert_eq!(vnd.split_suffix(), (parsed.subty, parsed.suffix));
+    }
+
+    #[test]
+    fn subtype_with_unsplittable_plus() {
+        const AMR_WB_PLUS_AUDIO: MediaType = MediaType::new(AUDIO, AMR_WB_PLUS);
+        for media_type in [
+            AMR_WB_PLUS_AUDIO,
+            MediaType::from_parts(AUDIO, AMR_WB_PLUS, None, &[]),
+            crate::media_type!(AUDIO / AMR_WB_PLUS),
+            crate::MediaTypeBuilder::new()
+                .ty(AUDIO)
+                .subty(AMR_WB_PLUS)
+                .build(),
+        ] {
+            assert_eq!(media_type.subty, "amr-wb+");
+            assert_eq!(media_type.suffix, None);
+            assert_eq!(media_type.to_string(), "audio/amr-wb+");
+            assert_eq!(MediaTypeBuf::from(&media_type).as_str(), "audio/amr-wb+");
+        }
+        assert_eq!(
+            MediaTypeBuf::new(AUDIO, AMR_WB_PLUS).as_str(),
+            "audio/amr-wb+"
+        );
+
+        let media_type =
+            MediaType::from_parts(IMAGE, Name::new("svg+xml").unwrap(), Some(ZIP), &[]);
+        assert_eq!(media_type.subty, "svg+xml");
+        assert_eq!(media_type.suffix, Some(ZIP));
+    }
+
+    #[test]
+    fn subtype_base() {
+        for s in [
+            "image/svg+xml",
+            "IMAGE/SVG+XML",
+            "image/svg+xml; charset=UTF-8",
+        ] {
+            let media_type = MediaType::parse(s).unwrap();
+            assert_eq!(media_type.subty, SVG);
+            assert_eq!(media_type.suffix, Some(XML));
+            assert_ne!(media_type.subty, "svg+xml");
+            assert_eq!(media_type.subtype_base(), (SVG, Some(XML)));
+        }
+        for s in ["image/svg+xml", "application/vnd.foo+xml+zip"] {
+            let media_type = MediaType::parse(s).unwrap();
+            let buf = MediaTypeBuf::from_str(s).unwrap();
+            assert_eq!(buf.subty(), media_type.subty);
+            assert_eq!(buf.suffix(), media_type.suffix);
+        }
+
+        let mut folded = MediaType::new(IMAGE, SVG);
+        folded.subty = Name::new("svg+xml").unwrap();
+        assert_eq!(folded.subtype_base(), (SVG, Some(XML)));
+        assert_eq!(MediaType::new(TEXT, PLAIN).subtype_base(), (PLAIN, None));
+    }
+
+    #[test]
+    fn multiple_suffixes() {
+        let media_type = MediaType::parse("application/vnd.foo+xml+zip; charset=UTF-8").unwrap();
+        assert_eq!(media_type.subty, "vnd.foo+xml");
+        assert_eq!(media_type.suffix, Some(ZIP));
+        assert_eq!(
+            media_type.to_string(),
+            "application/vnd.foo+xml+zip; charset=UTF-8"
+        );
+        assert_eq!(
+            MediaType::parse(&media_type.to_string()).unwrap(),
+            media_type
+        );
+    }
+
+    #[test]
+    fn const_eq() {
+        static A: MediaType = MediaType::from_parts(IMAGE, SVG, Some(XML), &[(CHARSET, UTF_8)]);
+        static B: MediaType = MediaType::from_parts(IMAGE, SVG, Some(XML), &[]);
+        const _: () = assert!(A.const_eq(&A));
+        const _: () = assert!(!A.const_eq(&B));
+
+        assert!(A.const_eq(&MediaType::parse("IMAGE/SVG+XML; Charset=UTF-8").unwrap()));
+        assert!(!A.const_eq(&MediaType::parse("image/svg+xml; charset=utf-8").unwrap()));
+        assert!(!A.const_eq(&MediaType::parse("image/svg; charset=UTF-8").unwrap()));
+        assert!(!A.const_eq(&MediaType::parse("image/svg+json; charset=UTF-8").unwrap()));
+        assert!(!A.const_eq(&MediaType::parse("image/png+xml; charset=UTF-8").unwrap()));
+        assert!(
+            !A.const_eq(&MediaType::parse("image/svg+xml; charset=UTF-8; format=fixed").unwrap())
+        );
+        assert!(!MediaType::new(TEXT, PLAIN).const_eq(&MediaType::new(TEXT, HTML)));
+    }
+
+    #[test]
+    fn const_get_param() {
+        static A: MediaType = MediaType::from_parts(
+            TEXT,
+            PLAIN,
+            None,
+            &[(CHARSET, US_ASCII), (FORMAT, FLOWED), (CHARSET, UTF_8)],
+        );
+        const CHARSET_OF: Option<Value> = A.const_get_param(CHARSET);
+        const BOUNDARY_OF: Option<Value> = A.const_get_param(BOUNDARY);
+        assert_eq!(CHARSET_OF, Some(UTF_8));
+        assert_eq!(BOUNDARY_OF, None);
+        assert_eq!(
+            A.const_get_param(Name::new("FORMAT").unwrap()),
+            Some(FLOWED)
+        );
+
+        let parsed = MediaType::parse("text/plain; Charset=UTF-8").unwrap();
+        assert_eq!(parsed.const_get_param(CHARSET), parsed.get_param(CHARSET));
+    }
+
+    #[test]
+    fn cmp_str() {
+        let media_type = MediaType::parse("image/svg+xml; charset=UTF-8").unwrap();
+        assert_eq!(media_type, "IMAGE/SVG+XML; CHARSET=UTF-8");
+        assert_eq!(media_type, *"image/svg+xml; charset=\"UTF-8\"");
+        assert_ne!(media_type, "image/svg+xml");
+        assert_ne!(media_type, "image/svg+xml; charset=utf-8");
+        assert_ne!(media_type, "image/svg+xml; charset");
+        assert!(MediaType::new(APPLICATION, JSON) == "application/json");
+    }
+
     #[test]
     fn hash() {
         assert_eq!(
@@ -379,5 +3363,33 @@ mod tests {
                 &[(CHARSET, US_ASCII), (CHARSET, UTF_8)]
             )),
         );
+        assert_ne!(
+            calculate_hash(&MediaType::parse("text/plain; charset=UTF-8").unwrap()),
+            calculate_hash(&MediaType::parse("text/plain; charset=utf-8").unwrap())
+        );
+    }
+
+    #[test]
+    fn hash_param_order() {
+        let inputs = [
+            "text/plain; a=1; b=2; c=3",
+            "text/plain; c=3; b=2; a=1",
+            "text/plain; b=2; A=\"1\"; c=3",
+            "text/plain; a=0; b=2; c=3; a=1",
+        ];
+        for a in inputs {
+            for b in inputs {
+                let a = MediaType::parse(a).unwrap();
+                let b = MediaType::parse(b).unwrap();
+                assert_eq!(a, b);
+                assert_eq!(calculate_hash(&a), calculate_hash(&b));
+                assert_eq!(calculate_hash(&a), calculate_hash(&MediaTypeBuf::from(&b)));
+            }
+        }
+
+        let a = MediaType::parse("text/plain; a=1; b=2").unwrap();
+        let b = MediaType::parse("text/plain; a=2; b=1").unwrap();
+        assert_ne!(a, b);
+        assert_ne!(calculate_hash(&a), calculate_hash(&b));
     }
 }
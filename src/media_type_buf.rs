@@ -1,7 +1,9 @@
-use super::{error::*, media_type::*, name::*, params::*, parse::*, value::*};
-use std::{
-    borrow::Cow,
-    collections::BTreeMap,
+use super::{
+    error::*, media_type::*, name::*, params::*, parse::*, parse_options::*, registration_tree::*,
+    value::*,
+};
+use alloc::{borrow::Cow, boxed::Box, string::String, vec::Vec};
+use core::{
     fmt,
     hash::{Hash, Hasher},
     str::FromStr,
@@ -19,7 +21,28 @@ use std::{
 /// text_markdown.subty = MARKDOWN;
 /// assert_eq!(text_markdown.to_string(), "text/markdown; charset=UTF-8");
 /// ```
-#[derive(Debug, Clone)]
+///
+/// # Map keys
+///
+/// `MediaTypeBuf` does not implement `Borrow<str>`. The `Borrow` contract requires
+/// the borrowed `str` to hash and compare the same way as the owner, but `MediaTypeBuf`
+/// compares names case-insensitively and ignores whitespace and parameter order, so
+/// `"TEXT/PLAIN"` and `"text/plain"` would be equal keys with different string hashes.
+///
+/// To look up a map by string, key it by the canonical form from
+/// [`MediaType::to_canonical`], or compare each key with [`matches_str`](Self::matches_str).
+///
+/// ```
+/// # use mediatype::{MediaType, MediaTypeBuf};
+/// # use std::collections::HashMap;
+/// let mut handlers = HashMap::new();
+/// let key = MediaType::parse("Text/Plain; Charset=UTF-8").unwrap().to_canonical();
+/// handlers.insert(key.into_string(), "plain");
+///
+/// let lookup = MediaType::parse("text/plain;charset=UTF-8").unwrap().to_canonical();
+/// assert_eq!(handlers.get(lookup.as_str()), Some(&"plain"));
+/// ```
+#[derive(Clone)]
 pub struct MediaTypeBuf {
     data: Box<str>,
     indices: Indices,
@@ -29,7 +52,7 @@ impl MediaTypeBuf {
     /// Constructs a `MediaTypeBuf` from a top-level type and a subtype.
     #[must_use]
     pub fn new(ty: Name, subty: Name) -> Self {
-        Self::from_string(format!("{}/{}", ty, subty)).expect("`ty` and `subty` should be valid")
+        Self::from(&MediaType::new(ty, subty))
     }
 
     /// Constructs a `MediaTypeBuf` with an optional suffix and parameters.
@@ -40,16 +63,71 @@ impl MediaTypeBuf {
         suffix: Option<Name>,
         params: &[(Name, Value)],
     ) -> Self {
-        use std::fmt::Write;
-        let mut s = String::new();
-        write!(s, "{}/{}", ty, subty).expect("`ty` and `subty` should be valid");
-        if let Some(suffix) = suffix {
-            write!(s, "+{}", suffix).unwrap();
+        Self::from(&MediaType::from_parts(ty, subty, suffix, params))
+    }
+
+    /// Constructs a `MediaTypeBuf` from owned strings, validating each of them.
+    ///
+    /// This is the inverse of [`MediaType::to_owned_parts`]. The position of
+    /// an error is the byte offset in the invalid piece.
+    ///
+    /// ```
+    /// # use mediatype::{MediaTypeBuf, MediaTypeError};
+    /// let parts = (
+    ///     "image".to_string(),
+    ///     "svg".to_string(),
+    ///     Some("xml".to_string()),
+    ///     vec![("charset".to_string(), Some("UTF-8".to_string()))],
+    /// );
+    /// let media_type = MediaTypeBuf::from_owned_parts(parts).unwrap();
+    /// assert_eq!(media_type.as_str(), "image/svg+xml; charset=UTF-8");
+    ///
+    /// let parts = ("text".to_string(), "plain; a=b".to_string(), None, vec![]);
+    /// assert_eq!(
+    ///     MediaTypeBuf::from_owned_parts(parts),
+    ///     Err(MediaTypeError::InvalidSubtypeName { position: 5 })
+    /// );
+    /// ```
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if any of the names or the values is not valid.
+    pub fn from_owned_parts(
+        (ty, subty, suffix, params): OwnedParts,
+    ) -> Result<Self, MediaTypeError> {
+        if let Some(position) = invalid_name_position(&ty) {
+            return Err(MediaTypeError::InvalidTypeName { position });
         }
-        for (name, value) in params {
-            write!(s, "; {}={}", name, value).unwrap();
+        if let Some(position) = invalid_name_position(&subty) {
+            return Err(MediaTypeError::InvalidSubtypeName { position });
         }
-        Self::from_string(s).expect("all values should be valid")
+        if let Some(position) = suffix.as_deref().and_then(invalid_name_position) {
+            return Err(MediaTypeError::InvalidSuffix { position });
+        }
+        let params = params
+            .iter()
+            .map(|(name, value)| {
+                if let Some(position) = invalid_name_position(name) {
+                    return Err(MediaTypeError::InvalidParamName { position });
+                }
+                let value = match value {
+                    Some(value) => {
+                        if let Some(position) = invalid_value_position(value) {
+                            return Err(MediaTypeError::InvalidParamValue { position });
+                        }
+                        Value::new_unchecked(value)
+                    }
+                    None => Value::ABSENT,
+                };
+                Ok((Name::new_unchecked(name), value))
+            })
+            .collect::<Result<Vec<_>, _>>()?;
+        Ok(Self::from_parts(
+            Name::new_unchecked(&ty),
+            Name::new_unchecked(&subty),
+            suffix.as_deref().map(Name::new_unchecked),
+            &params,
+        ))
     }
 
     /// Constructs a `MediaTypeBuf` from [`String`].
@@ -63,8 +141,22 @@ impl MediaTypeBuf {
     ///
     /// [`String`]: https://doc.rust-lang.org/std/string/struct.String.html
     /// [`FromStr::from_str`]: https://doc.rust-lang.org/std/str/trait.FromStr.html
-    pub fn from_string(mut s: String) -> Result<Self, MediaTypeError> {
-        let (indices, len) = Indices::parse(&s)?;
+    pub fn from_string(s: String) -> Result<Self, MediaTypeError> {
+        Self::from_string_with(s, &ParseOptions::UNLIMITED)
+    }
+
+    /// Parses a string written from valid parts, which may have parameters without a value.
+    pub(crate) fn from_written(s: String) -> Result<Self, MediaTypeError> {
+        Self::from_string_with(s, &ParseOptions::UNLIMITED.with_relaxed(true))
+    }
+
+    pub(crate) fn from_string_with(
+        mut s: String,
+        options: &ParseOptions,
+    ) -> Result<Self, MediaTypeError> {
+        let start = trim_start_ows(&s).1;
+        s.drain(..start);
+        let (indices, len) = Indices::parse_with(&s, options).map_err(|err| err.offset(start))?;
         s.truncate(len);
         Ok(Self {
             data: s.into(),
@@ -74,19 +166,19 @@ impl MediaTypeBuf {
 
     /// Returns the top-level type.
     #[must_use]
-    pub fn ty(&self) -> Name {
+    pub fn ty(&self) -> Name<'_> {
         Name::new_unchecked(&self.data[self.indices.ty()])
     }
 
     /// Returns the subtype.
     #[must_use]
-    pub fn subty(&self) -> Name {
+    pub fn subty(&self) -> Name<'_> {
         Name::new_unchecked(&self.data[self.indices.subty()])
     }
 
     /// Returns the suffix.
     #[must_use]
-    pub fn suffix(&self) -> Option<Name> {
+    pub fn suffix(&self) -> Option<Name<'_>> {
         self.indices
             .suffix()
             .map(|range| Name::new_unchecked(&self.data[range]))
@@ -107,12 +199,61 @@ impl MediaTypeBuf {
         MediaType::from_parts(self.ty(), self.subty(), self.suffix(), &[])
     }
 
+    /// Returns `true` if the essence is registered in the IANA Media Types registry.
+    ///
+    /// See [`MediaType::is_registered`].
+    #[cfg(feature = "iana")]
+    #[cfg_attr(docsrs, doc(cfg(feature = "iana")))]
+    #[must_use]
+    pub fn is_registered(&self) -> bool {
+        crate::consts::is_registered(self.ty(), self.subty(), self.suffix())
+    }
+
+    /// Returns the registration tree of the subtype.
+    #[must_use]
+    pub fn registration_tree(&self) -> RegistrationTree {
+        RegistrationTree::of(self.subty())
+    }
+
+    /// Returns `true` if `s` parses to a media type equal to `self`.
+    ///
+    /// Returns `false` if `s` fails to be parsed. This is the same as `self == s`.
+    ///
+    /// ```
+    /// # use mediatype::MediaTypeBuf;
+    /// let media_type: MediaTypeBuf = "text/plain; charset=UTF-8".parse().unwrap();
+    /// assert!(media_type.matches_str("TEXT/PLAIN;charset=\"UTF-8\""));
+    /// assert!(!media_type.matches_str("text/plain"));
+    /// assert!(!media_type.matches_str("text"));
+    /// ```
+    #[must_use]
+    pub fn matches_str(&self, s: &str) -> bool {
+        MediaType::parse(s).is_ok_and(|other| *self == other)
+    }
+
     /// Returns the underlying string.
     #[must_use]
     pub const fn as_str(&self) -> &str {
         &self.data
     }
 
+    /// Converts `self` into the underlying [`String`] without reallocating.
+    ///
+    /// The string is the same as [`as_str`](Self::as_str). It keeps the original case
+    /// and whitespace; use [`canonicalize`](Self::canonicalize) first to get a normalized one.
+    ///
+    /// ```
+    /// # use mediatype::MediaTypeBuf;
+    /// let media_type: MediaTypeBuf = "IMAGE/SVG+XML;  CHARSET=UTF-8;  ".parse().unwrap();
+    /// assert_eq!(media_type.canonicalize().into_string(), "image/svg+xml; charset=UTF-8");
+    /// ```
+    ///
+    /// [`String`]: https://doc.rust-lang.org/std/string/struct.String.html
+    #[must_use]
+    pub fn into_string(self) -> String {
+        self.data.into_string()
+    }
+
     /// Returns the canonicalized `MediaTypeBuf`.
     ///
     /// All strings except parameter values will be converted to lowercase.
@@ -127,7 +268,7 @@ impl MediaTypeBuf {
     /// ```
     #[must_use]
     pub fn canonicalize(&self) -> Self {
-        use std::fmt::Write;
+        use core::fmt::Write;
         let mut s = String::with_capacity(self.data.len());
         write!(
             s,
@@ -141,16 +282,69 @@ impl MediaTypeBuf {
                 .expect("`write` should not fail on a `String`");
         }
         for (name, value) in self.params() {
-            write!(s, "; {}={}", name.as_str().to_ascii_lowercase(), value)
+            write_param(&mut s, name.as_str().to_ascii_lowercase(), value)
                 .expect("`write` should not fail on a `String`");
         }
         s.shrink_to_fit();
-        Self::from_string(s).expect("all values should be valid")
+        Self::from_written(s).expect("all values should be valid")
+    }
+
+    /// Appends a parameter to the underlying string without parsing it again.
+    ///
+    /// Existing parameters with the same name are not removed, but the new value
+    /// takes precedence in [`ReadParams::get_param`]. `value` is written as it is,
+    /// so use [`Value::try_quote`] to construct a value that needs quoting.
+    ///
+    /// The string and the parameter indices are stored as boxed slices, so each call
+    /// reallocates both of them. To build a media type with many parameters,
+    /// construct a [`MediaType`] and convert it with [`MediaTypeBuf::from`] instead.
+    ///
+    /// ```
+    /// # use mediatype::{names::*, values::*, MediaTypeBuf, ReadParams, Value};
+    /// let mut media_type = MediaTypeBuf::new(MULTIPART, FORM_DATA);
+    /// media_type.push_param(CHARSET, UTF_8);
+    ///
+    /// let boundary = Value::try_quote("a b").unwrap();
+    /// media_type.push_param(BOUNDARY, Value::new(&boundary).unwrap());
+    /// assert_eq!(
+    ///     media_type.as_str(),
+    ///     "multipart/form-data; charset=UTF-8; boundary=\"a b\""
+    /// );
+    /// assert_eq!(media_type.get_param(BOUNDARY).unwrap().unquoted(), "a b");
+    /// ```
+    pub fn push_param(&mut self, name: Name, value: Value) {
+        let mut data = core::mem::take(&mut self.data).into_string();
+        data.push_str("; ");
+        let name_start = data.len();
+        data.push_str(name.as_str());
+        let name_end = data.len();
+        if !value.is_absent() {
+            data.push('=');
+        }
+        let value_start = data.len();
+        data.push_str(value.as_str());
+        self.indices
+            .push_param([name_start, name_end, value_start, data.len()]);
+        self.data = data.into();
     }
 
     /// Constructs a `MediaType` from `self`.
+    ///
+    /// The names and the values borrow the substrings of `self`, which are already
+    /// validated, so the string is not parsed again. If there are no parameters,
+    /// this does not allocate; otherwise the `(Name, Value)` pairs are collected into
+    /// a [`Vec`] as [`MediaType::params`] is a slice.
+    ///
+    /// ```
+    /// # use mediatype::{MediaType, MediaTypeBuf};
+    /// let media_type: MediaTypeBuf = "image/svg+xml; charset=UTF-8".parse().unwrap();
+    /// let borrowed = media_type.to_ref();
+    /// assert_eq!(borrowed.ty.as_str().as_ptr(), media_type.as_str().as_ptr());
+    /// ```
+    ///
+    /// [`Vec`]: https://doc.rust-lang.org/std/vec/struct.Vec.html
     #[must_use]
-    pub fn to_ref(&self) -> MediaType {
+    pub fn to_ref(&self) -> MediaType<'_> {
         let params = self.params().collect::<Vec<_>>();
         let params = if params.is_empty() {
             Cow::Borrowed([].as_slice())
@@ -162,21 +356,24 @@ impl MediaTypeBuf {
 }
 
 impl ReadParams for MediaTypeBuf {
-    fn params(&self) -> Params {
+    fn params(&self) -> Params<'_> {
         Params::from_indices(&self.data, &self.indices)
     }
 
-    fn get_param(&self, name: Name) -> Option<Value> {
+    fn get_param(&self, name: Name) -> Option<Value<'_>> {
         self.indices
             .params()
             .iter()
             .rev()
-            .find(|&&[start, end, _, _]| {
-                name == Name::new_unchecked(&self.data[start..end])
-            })
-            .map(|&[_, _, start, end]| {
-                Value::new_unchecked(&self.data[start..end])
-            })
+            .find(|&&[start, end, _, _]| name == Name::new_unchecked(&self.data[start..end]))
+            .map(|param| Value::from_param(&self.data, param))
+    }
+
+    fn has_param(&self, name: Name) -> bool {
+        self.indices
+            .params()
+            .iter()
+            .any(|&[start, end, _, _]| name == Name::new_unchecked(&self.data[start..end]))
     }
 }
 
@@ -184,7 +381,8 @@ impl FromStr for MediaTypeBuf {
     type Err = MediaTypeError;
 
     fn from_str(s: &str) -> Result<Self, Self::Err> {
-        let (indices, len) = Indices::parse(s)?;
+        let (s, start) = trim_start_ows(s);
+        let (indices, len) = Indices::parse(s).map_err(|err| err.offset(start))?;
         Ok(Self {
             data: s[..len].into(),
             indices,
@@ -194,13 +392,51 @@ impl FromStr for MediaTypeBuf {
 
 impl From<MediaType<'_>> for MediaTypeBuf {
     fn from(t: MediaType) -> Self {
-        Self::from_string(t.to_string()).expect("`t` should be valid")
+        Self::from(&t)
     }
 }
 
+/// Copies the names and the values without parsing them again.
+///
+/// If the suffix is not set, a subtype containing `+` is split
+/// like [`MediaType::new`] does.
 impl From<&MediaType<'_>> for MediaTypeBuf {
     fn from(t: &MediaType) -> Self {
-        Self::from_string(t.to_string()).expect("`t` should be valid")
+        let (subty, suffix) = split_subtype(t.subty, t.suffix);
+        let mut data = String::new();
+        data.push_str(t.ty.as_str());
+        data.push('/');
+        data.push_str(subty.as_str());
+        if let Some(suffix) = suffix {
+            data.push('+');
+            data.push_str(suffix.as_str());
+        }
+        let params = t
+            .params
+            .iter()
+            .map(|(name, value)| {
+                data.push_str("; ");
+                let name_start = data.len();
+                data.push_str(name.as_str());
+                let name_end = data.len();
+                if !value.is_absent() {
+                    data.push('=');
+                }
+                let value_start = data.len();
+                data.push_str(value.as_str());
+                [name_start, name_end, value_start, data.len()]
+            })
+            .collect();
+        let indices = Indices::from_valid_parts(
+            t.ty.len(),
+            subty.len(),
+            suffix.map_or(0, |suffix| suffix.len()),
+            params,
+        );
+        Self {
+            data: data.into(),
+            indices,
+        }
     }
 }
 
@@ -215,8 +451,7 @@ impl PartialEq for MediaTypeBuf {
         self.ty() == other.ty()
             && self.subty() == other.subty()
             && self.suffix() == other.suffix()
-            && self.params().collect::<BTreeMap<_, _>>()
-                == other.params().collect::<BTreeMap<_, _>>()
+            && self.params().set_eq(other.params())
     }
 }
 
@@ -227,8 +462,7 @@ impl PartialEq<MediaType<'_>> for MediaTypeBuf {
         self.ty() == other.ty
             && self.subty() == other.subty
             && self.suffix() == other.suffix
-            && self.params().collect::<BTreeMap<_, _>>()
-                == other.params().collect::<BTreeMap<_, _>>()
+            && self.params().set_eq(other.params())
     }
 }
 
@@ -244,14 +478,44 @@ impl PartialEq<MediaType<'_>> for &MediaTypeBuf {
     }
 }
 
+impl PartialEq<str> for MediaTypeBuf {
+    fn eq(&self, other: &str) -> bool {
+        self.matches_str(other)
+    }
+}
+
+impl PartialEq<&str> for MediaTypeBuf {
+    fn eq(&self, other: &&str) -> bool {
+        self == *other
+    }
+}
+
+/// Formats the names and the values as strings, hiding the internal representation.
+impl fmt::Debug for MediaTypeBuf {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        debug_fields(
+            f,
+            "MediaTypeBuf",
+            self.ty(),
+            self.subty(),
+            self.suffix(),
+            self.params(),
+        )
+    }
+}
+
+/// The alternate format `{:#}` writes only the type, the subtype and the suffix.
 impl fmt::Display for MediaTypeBuf {
     fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
         write!(f, "{}/{}", self.ty(), self.subty())?;
         if let Some(suffix) = self.suffix() {
             write!(f, "+{}", suffix)?;
         }
+        if f.alternate() {
+            return Ok(());
+        }
         for (name, value) in self.params() {
-            write!(f, "; {}={}", name, value)?;
+            write_param(f, name, value)?;
         }
         Ok(())
     }
@@ -262,11 +526,7 @@ impl Hash for MediaTypeBuf {
         self.ty().hash(state);
         self.subty().hash(state);
         self.suffix().hash(state);
-        self.params()
-            .collect::<BTreeMap<_, _>>()
-            .into_iter()
-            .collect::<Vec<_>>()
-            .hash(state);
+        self.params().set_hash(state);
     }
 }
 
@@ -290,6 +550,79 @@ mod tests {
         );
     }
 
+    #[test]
+    fn from_str() {
+        let text_plain: MediaTypeBuf = "text/plain; charset=UTF-8".parse().unwrap();
+        assert_eq!(text_plain.as_str(), "text/plain; charset=UTF-8");
+        assert_eq!(
+            "textplain".parse::<MediaTypeBuf>(),
+            Err(MediaTypeError::MissingSlash { position: 9 })
+        );
+
+        let list = ["text/plain", "image/svg+xml"]
+            .iter()
+            .map(|s| s.parse())
+            .collect::<Result<Vec<MediaTypeBuf>, _>>()
+            .unwrap();
+        assert_eq!(
+            list,
+            [media_type!(TEXT / PLAIN), media_type!(IMAGE / SVG + XML)]
+        );
+
+        let list = ["text/plain", "image"]
+            .iter()
+            .map(|s| s.parse())
+            .collect::<Result<Vec<MediaTypeBuf>, _>>();
+        assert_eq!(list, Err(MediaTypeError::MissingSlash { position: 5 }));
+
+        let media_type = MediaTypeBuf::from_str("  text/plain; charset=UTF-8\t").unwrap();
+        assert_eq!(media_type.as_str(), "text/plain; charset=UTF-8");
+        assert_eq!(media_type.get_param(CHARSET), Some(UTF_8));
+        let media_type = MediaTypeBuf::from_string(" \timage/svg+xml ".into()).unwrap();
+        assert_eq!(media_type.as_str(), "image/svg+xml");
+        assert_eq!(media_type.suffix(), Some(XML));
+        assert_eq!(
+            MediaTypeBuf::from_string("  text /plain".into()),
+            Err(MediaTypeError::InvalidTypeName { position: 6 })
+        );
+
+        assert_eq!(
+            MediaTypeBuf::from_str("text/plain garbage"),
+            Err(MediaTypeError::TrailingData { position: 11 })
+        );
+        assert_eq!(
+            MediaTypeBuf::from_str("text/plain; a=1 ;").map(|media_type| media_type.to_string()),
+            Ok("text/plain; a=1".into())
+        );
+    }
+
+    #[test]
+    fn long_input() {
+        let value = "a".repeat(70_000);
+        let media_type =
+            MediaTypeBuf::from_string(format!("text/plain; x=\"{value}\"; charset=UTF-8")).unwrap();
+        let x = media_type.get_param(Name::new("x").unwrap()).unwrap();
+        assert_eq!(x.unquoted(), value);
+        assert_eq!(media_type.get_param(CHARSET), Some(UTF_8));
+        assert_eq!(media_type.params().len(), 2);
+
+        assert_eq!(
+            MediaTypeBuf::from_string(format!("{value}/plain")),
+            Err(MediaTypeError::InvalidTypeName { position: 127 })
+        );
+        assert_eq!(
+            MediaTypeBuf::from_string(format!("text/{value}")),
+            Err(MediaTypeError::InvalidSubtypeName { position: 132 })
+        );
+        assert_eq!(
+            MediaTypeBuf::from_string_with(
+                format!("text/plain; x={value}"),
+                &ParseOptions::default()
+            ),
+            Err(MediaTypeError::TooLarge { position: 4096 })
+        );
+    }
+
     #[test]
     fn get_param() {
         assert_eq!(
@@ -312,6 +645,189 @@ mod tests {
         );
     }
 
+    #[test]
+    fn quality() {
+        assert_eq!(MediaTypeBuf::from_str("text/html").unwrap().quality(), 1.0);
+        assert_eq!(
+            MediaTypeBuf::from_str("text/html; q=0.25")
+                .unwrap()
+                .quality(),
+            0.25
+        );
+    }
+
+    #[test]
+    fn charset() {
+        assert_eq!(
+            MediaTypeBuf::from_str("text/plain").unwrap().charset(),
+            None
+        );
+        assert_eq!(
+            MediaTypeBuf::from_str("text/plain; charset=UTF-8")
+                .unwrap()
+                .charset(),
+            Some(UTF_8)
+        );
+    }
+
+    #[test]
+    fn has_param() {
+        assert!(!MediaTypeBuf::from_str("image/svg+xml")
+            .unwrap()
+            .has_param(CHARSET));
+        assert!(MediaTypeBuf::from_str("image/svg+xml; CHARSET=UTF-8")
+            .unwrap()
+            .has_param(CHARSET));
+    }
+
+    #[test]
+    fn params_len() {
+        let media_type = MediaTypeBuf::from_str("image/svg+xml").unwrap();
+        assert_eq!(media_type.params_len(), 0);
+        assert!(!media_type.has_params());
+
+        let media_type = MediaTypeBuf::from_str("image/svg+xml; charset=UTF-8; a=b").unwrap();
+        assert_eq!(media_type.params_len(), 2);
+        assert!(media_type.has_params());
+
+        let mut params = media_type.params();
+        params.next();
+        assert_eq!(params.len(), 1);
+        params.next();
+        assert_eq!(params.len(), 0);
+    }
+
+    #[test]
+    fn param_names() {
+        let media_type =
+            MediaTypeBuf::from_str("image/svg+xml; charset=UTF-8; a=b; CHARSET=US-ASCII").unwrap();
+        let names = media_type.param_names();
+        assert_eq!(names.len(), 3);
+        assert_eq!(
+            names.map(|name| name.as_str()).collect::<Vec<_>>(),
+            ["charset", "a", "CHARSET"]
+        );
+        assert_eq!(
+            MediaTypeBuf::from_str("text/plain")
+                .unwrap()
+                .param_names()
+                .next(),
+            None
+        );
+    }
+
+    #[test]
+    fn push_param() {
+        let mut media_type = MediaTypeBuf::from_str("text/plain; charset=US-ASCII").unwrap();
+        media_type.push_param(FORMAT, FLOWED);
+        media_type.push_param(CHARSET, Value::new("\"UTF-8\"").unwrap());
+        assert_eq!(
+            media_type.as_str(),
+            "text/plain; charset=US-ASCII; format=flowed; charset=\"UTF-8\""
+        );
+        assert_eq!(media_type.get_param(CHARSET), Some(UTF_8));
+        assert_eq!(media_type.params_len(), 3);
+        assert_eq!(
+            media_type,
+            MediaTypeBuf::from_str(media_type.as_str()).unwrap()
+        );
+        assert_eq!(media_type.ty(), TEXT);
+        assert_eq!(media_type.subty(), PLAIN);
+    }
+
+    #[test]
+    fn param_without_value() {
+        let flag = Name::new("flag").unwrap();
+        assert_eq!(
+            MediaTypeBuf::from_str("text/plain; flag"),
+            Err(MediaTypeError::InvalidParams { position: 16 })
+        );
+        let media_type = MediaTypeBuf::from_parts(TEXT, PLAIN, None, &[(flag, Value::ABSENT)]);
+        assert_eq!(media_type.get_param(flag), Some(Value::ABSENT));
+        assert_eq!(media_type.to_string(), "text/plain; flag");
+        assert_eq!(media_type.canonicalize().as_str(), "text/plain; flag");
+
+        let mut pushed = MediaTypeBuf::from_str("text/plain").unwrap();
+        pushed.push_param(flag, Value::ABSENT);
+        pushed.push_param(CHARSET, UTF_8);
+        assert_eq!(pushed.as_str(), "text/plain; flag; charset=UTF-8");
+        assert_eq!(pushed.get_param(flag), Some(Value::ABSENT));
+
+        let parsed = MediaType::parse_relaxed("text/plain; flag; charset=UTF-8").unwrap();
+        let converted = MediaTypeBuf::from(&parsed);
+        assert_eq!(converted.as_str(), "text/plain; flag; charset=UTF-8");
+        assert_eq!(converted.get_param(flag), Some(Value::ABSENT));
+
+        let empty = MediaTypeBuf::from_str("text/plain; flag=").unwrap();
+        assert_eq!(empty.get_param(flag), Some(Value::EMPTY));
+        assert_eq!(empty.to_string(), "text/plain; flag=");
+    }
+
+    #[test]
+    fn params_get() {
+        let media_type = MediaTypeBuf::from_str("image/svg+xml; charset=UTF-8; a=b").unwrap();
+        let mut params = media_type.params();
+        assert_eq!(params.get(0), Some((CHARSET, UTF_8)));
+        assert_eq!(params.get(2), None);
+        assert_eq!(params.get(usize::MAX), None);
+
+        assert_eq!(params.next(), Some((CHARSET, UTF_8)));
+        assert_eq!(params.len(), 1);
+        assert_eq!(params.size_hint(), (1, Some(1)));
+        assert_eq!(params.get(0).map(|(name, _)| name.as_str()), Some("a"));
+        params.next();
+        assert!(params.is_empty());
+        assert_eq!(params.get(0), None);
+
+        let media_type = MediaTypeBuf::from_str("image/svg+xml").unwrap();
+        assert!(media_type.params().is_empty());
+    }
+
+    #[test]
+    fn params_as_map() {
+        let media_type =
+            MediaTypeBuf::from_str("text/plain; Charset=US-ASCII; a=b; CHARSET=UTF-8").unwrap();
+        let params = media_type.params_as_map();
+        assert_eq!(params.len(), 2);
+        assert_eq!(params.get(&CHARSET), media_type.get_param(CHARSET).as_ref());
+        assert_eq!(
+            params.keys().map(|name| name.as_str()).collect::<Vec<_>>(),
+            ["a", "CHARSET"]
+        );
+        assert!(MediaTypeBuf::from_str("text/plain")
+            .unwrap()
+            .params_as_map()
+            .is_empty());
+    }
+
+    #[test]
+    fn params_rev() {
+        let media_type =
+            MediaTypeBuf::from_str("text/plain; charset=US-ASCII; a=b; charset=UTF-8").unwrap();
+        let params = media_type.params_rev().collect::<Vec<_>>();
+        assert_eq!(params.len(), 3);
+        assert_eq!(params[0], (CHARSET, UTF_8));
+        assert_eq!(params[2], (CHARSET, US_ASCII));
+
+        let mut params = media_type.params();
+        assert_eq!(params.next_back(), Some((CHARSET, UTF_8)));
+        assert_eq!(params.next(), Some((CHARSET, US_ASCII)));
+        assert_eq!(params.len(), 1);
+        assert_eq!(params.get(1), None);
+        assert_eq!(params.next_back().map(|(name, _)| name.as_str()), Some("a"));
+        assert_eq!(params.next(), None);
+        assert_eq!(params.next_back(), None);
+
+        let media_type = MediaType::parse("text/plain; charset=US-ASCII; charset=UTF-8").unwrap();
+        assert_eq!(
+            media_type
+                .params_rev()
+                .map(|(_, value)| value)
+                .collect::<Vec<_>>(),
+            [UTF_8, US_ASCII]
+        );
+    }
+
     #[test]
     fn essence() {
         assert_eq!(
@@ -344,6 +860,27 @@ mod tests {
         );
     }
 
+    #[test]
+    fn as_str() {
+        fn len<S: AsRef<str>>(s: S) -> usize {
+            s.as_ref().len()
+        }
+
+        let media_type = MediaTypeBuf::from_str("image/svg+xml; charset=UTF-8;  ").unwrap();
+        assert_eq!(media_type.as_str(), "image/svg+xml; charset=UTF-8");
+        assert_eq!(media_type.as_ref(), media_type.as_str());
+        assert_eq!(len(&media_type), "image/svg+xml; charset=UTF-8".len());
+    }
+
+    #[test]
+    fn into_string() {
+        let media_type = MediaTypeBuf::from_str("image/svg+xml;  charset=UTF-8;  ").unwrap();
+        let ptr = media_type.as_str().as_ptr();
+        let s = media_type.into_string();
+        assert_eq!(s, "image/svg+xml;  charset=UTF-8");
+        assert_eq!(s.as_ptr(), ptr);
+    }
+
     #[test]
     fn canonicalize() {
         assert_eq!(
@@ -382,6 +919,32 @@ mod tests {
             &MediaTypeBuf::from_str("image/svg+xml").unwrap(),
             media_type!(IMAGE / SVG + XML)
         );
+        assert_ne!(
+            MediaTypeBuf::from_str("text/plain; charset=UTF-8").unwrap(),
+            MediaTypeBuf::from_str("text/plain; charset=utf-8").unwrap()
+        );
+
+        let buf = MediaTypeBuf::from_str("TEXT/PLAIN; charset=UTF-8").unwrap();
+        let media_type = MediaType::parse("text/plain; CHARSET=UTF-8").unwrap();
+        assert_eq!(buf, media_type);
+        assert_eq!(media_type, buf);
+        assert_eq!(&buf, media_type);
+        assert_eq!(media_type, &buf);
+        assert_eq!(buf, &media_type);
+        assert_eq!(&media_type, buf);
+
+        let other = MediaType::parse("text/plain; charset=utf-8").unwrap();
+        assert_ne!(buf, other);
+        assert_ne!(other, buf);
+    }
+
+    #[test]
+    fn cmp_str() {
+        let media_type = MediaTypeBuf::from_str("image/svg+xml; charset=UTF-8").unwrap();
+        assert_eq!(media_type, "IMAGE/SVG+XML; CHARSET=UTF-8");
+        assert_eq!(media_type, *"image/svg+xml; charset=UTF-8");
+        assert_ne!(media_type, "image/svg+xml; charset=utf-8");
+        assert_ne!(media_type, "image/svg+xml;;");
     }
 
     #[test]
@@ -28,10 +28,8 @@ impl<'a> MediaTypeList<'a> {
     }
 }
 
-impl<'a> Iterator for MediaTypeList<'a> {
-    type Item = Result<MediaType<'a>, MediaTypeError>;
-
-    fn next(&mut self) -> Option<Self::Item> {
+impl<'a> MediaTypeList<'a> {
+    pub(crate) fn next_str(&mut self) -> Option<&'a str> {
         if let Some(index) = self.0.find(|c| !is_ows(c)) {
             self.0 = &self.0[index..];
         } else {
@@ -56,10 +54,18 @@ impl<'a> Iterator for MediaTypeList<'a> {
             }
             end += 1;
         }
-        let madia_type = MediaType::parse(&self.0[..end]);
-        let end = self.0.as_bytes().len().min(end + 1);
+        let item = &self.0[..end];
+        let end = self.0.len().min(end + 1);
         self.0 = &self.0[end..];
-        Some(madia_type)
+        Some(item)
+    }
+}
+
+impl<'a> Iterator for MediaTypeList<'a> {
+    type Item = Result<MediaType<'a>, MediaTypeError>;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        self.next_str().map(MediaType::parse)
     }
 
     fn size_hint(&self) -> (usize, Option<usize>) {
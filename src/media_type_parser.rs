@@ -0,0 +1,318 @@
+use super::{error::*, media_type_buf::*, name::*, parse::*, parse_options::*};
+use alloc::{string::String, vec::Vec};
+
+/// An incremental parser for a media type split across multiple buffers.
+///
+/// The bytes are copied into the buffer which becomes the storage of the resulting
+/// [`MediaTypeBuf`], so the input does not have to be accumulated beforehand.
+/// The top-level type and the length limit are checked while feeding,
+/// and [`error`](Self::error) reports such errors before the input ends.
+/// The rest is validated by [`finish`](Self::finish).
+///
+/// ```
+/// use mediatype::{names::*, values::*, MediaTypeParser, ReadParams};
+///
+/// let mut parser = MediaTypeParser::new();
+/// parser.feed(b"text/pl");
+/// parser.feed(b"ain; charset=UT");
+/// parser.feed(b"F-8");
+///
+/// let media_type = parser.finish().unwrap();
+/// assert_eq!(media_type.subty(), PLAIN);
+/// assert_eq!(media_type.get_param(CHARSET), Some(UTF_8));
+/// ```
+#[derive(Debug, Clone)]
+pub struct MediaTypeParser {
+    buf: Vec<u8>,
+    options: ParseOptions,
+    skipped: usize,
+    bom: usize,
+    state: State,
+}
+
+#[derive(Debug, Copy, Clone, PartialEq, Eq)]
+enum State {
+    Type,
+    Rest,
+    Failed(MediaTypeError),
+}
+
+impl MediaTypeParser {
+    /// Constructs a `MediaTypeParser` without limits, like [`MediaTypeBuf::from_string`].
+    #[must_use]
+    pub const fn new() -> Self {
+        Self::with_options(ParseOptions::UNLIMITED)
+    }
+
+    /// Constructs a `MediaTypeParser` which parses like [`MediaType::parse_with`].
+    ///
+    /// An input exceeding [`ParseOptions::max_length`] fails as soon as it is fed.
+    ///
+    /// ```
+    /// # use mediatype::{MediaTypeError, MediaTypeParser, ParseOptions};
+    /// let mut parser = MediaTypeParser::with_options(ParseOptions::new().with_max_length(16));
+    /// parser.feed(b"text/plain; ");
+    /// assert_eq!(parser.error(), None);
+    /// parser.feed(b"charset=UTF-8");
+    /// assert_eq!(parser.error(), Some(MediaTypeError::TooLarge { position: 16 }));
+    /// ```
+    ///
+    /// [`MediaType::parse_with`]: crate::MediaType::parse_with
+    #[must_use]
+    pub const fn with_options(options: ParseOptions) -> Self {
+        Self {
+            buf: Vec::new(),
+            options,
+            skipped: 0,
+            bom: 0,
+            state: State::Type,
+        }
+    }
+
+    /// Feeds the next part of the input.
+    ///
+    /// Once an error is detected, the subsequent input is ignored.
+    pub fn feed(&mut self, mut bytes: &[u8]) {
+        while self.state == State::Type {
+            match bytes.split_first() {
+                Some((&b, rest)) => {
+                    self.feed_type(b);
+                    bytes = rest;
+                }
+                None => return,
+            }
+        }
+        if self.state == State::Rest {
+            if self.buf.len() + bytes.len() > self.options.max_length {
+                self.fail(MediaTypeError::TooLarge {
+                    position: self.options.max_length,
+                });
+            } else {
+                self.buf.extend_from_slice(bytes);
+            }
+        }
+    }
+
+    fn feed_type(&mut self, b: u8) {
+        let position = self.buf.len();
+        if position == 0 && self.bom == self.skipped && self.bom < BOM.len() {
+            if b == BOM[self.bom] {
+                self.bom += 1;
+                self.skipped += 1;
+                return;
+            }
+            if self.bom > 0 {
+                // An incomplete BOM is not valid UTF-8.
+                self.state = State::Failed(MediaTypeError::InvalidEncoding { position: 0 });
+                return;
+            }
+        }
+        if position == 0 && is_ows(b.into()) {
+            self.skipped += 1;
+            return;
+        }
+        if position >= self.options.max_length {
+            self.fail(MediaTypeError::TooLarge {
+                position: self.options.max_length,
+            });
+            return;
+        }
+        if b == b'/' && position == 0 {
+            self.fail(MediaTypeError::EmptyType { position });
+            return;
+        } else if b == b'/' {
+            self.state = State::Rest;
+        } else if position >= Name::MAX_LENGTH {
+            self.fail(MediaTypeError::InvalidTypeName {
+                position: Name::MAX_LENGTH,
+            });
+            return;
+        } else if (position == 0 && !(b.is_ascii_alphanumeric() || b == b'*'))
+            || !is_restricted_char(b.into())
+        {
+            self.fail(MediaTypeError::InvalidTypeName { position });
+            return;
+        }
+        self.buf.push(b);
+    }
+
+    fn fail(&mut self, err: MediaTypeError) {
+        self.state = State::Failed(err.offset(self.skipped));
+        self.buf = Vec::new();
+    }
+
+    /// Returns the error detected so far, if any.
+    ///
+    /// ```
+    /// # use mediatype::{MediaTypeError, MediaTypeParser};
+    /// let mut parser = MediaTypeParser::new();
+    /// parser.feed(b"te");
+    /// parser.feed(b"x t/plain");
+    /// assert_eq!(parser.error(), Some(MediaTypeError::InvalidTypeName { position: 3 }));
+    /// ```
+    #[must_use]
+    pub const fn error(&self) -> Option<MediaTypeError> {
+        match self.state {
+            State::Failed(err) => Some(err),
+            _ => None,
+        }
+    }
+
+    /// Parses the fed input as a [`MediaTypeBuf`].
+    ///
+    /// The positions in errors are relative to the whole input.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the input fails to be parsed,
+    /// or [`MediaTypeError::InvalidEncoding`] if it is not valid UTF-8.
+    pub fn finish(self) -> Result<MediaTypeBuf, MediaTypeError> {
+        if let State::Failed(err) = self.state {
+            return Err(err);
+        }
+        if self.bom > 0 && self.bom < BOM.len() {
+            return Err(MediaTypeError::InvalidEncoding { position: 0 });
+        }
+        let s = String::from_utf8(self.buf).map_err(|err| MediaTypeError::InvalidEncoding {
+            position: err.utf8_error().valid_up_to() + self.skipped,
+        })?;
+        MediaTypeBuf::from_string_with(s, &self.options).map_err(|err| err.offset(self.skipped))
+    }
+}
+
+impl Default for MediaTypeParser {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::MediaType;
+
+    fn parse_chunks(
+        chunks: &[&[u8]],
+        options: ParseOptions,
+    ) -> Result<MediaTypeBuf, MediaTypeError> {
+        let mut parser = MediaTypeParser::with_options(options);
+        for chunk in chunks {
+            parser.feed(chunk);
+        }
+        parser.finish()
+    }
+
+    #[test]
+    fn feed() {
+        let input = "image/svg+xml; charset=UTF-8; hello=\"a b\\\"c\"";
+        for i in 0..=input.len() {
+            for j in i..=input.len() {
+                let (a, rest) = input.as_bytes().split_at(i);
+                let (b, c) = rest.split_at(j - i);
+                let media_type = parse_chunks(&[a, b, c], ParseOptions::UNLIMITED).unwrap();
+                assert_eq!(media_type, MediaType::parse(input).unwrap());
+                assert_eq!(media_type.as_str(), input);
+            }
+        }
+        assert_eq!(
+            MediaTypeParser::new().finish(),
+            MediaTypeBuf::from_string(String::new())
+        );
+    }
+
+    #[test]
+    fn errors() {
+        for input in [
+            "",
+            "text",
+            "/plain",
+            "-text/plain",
+            "te xt/plain",
+            "text/",
+            "text/plain;",
+            "text/plain; charset",
+            "text/plain; charset=\"UTF-8",
+        ] {
+            let chunks: Vec<&[u8]> = input.as_bytes().chunks(2).collect();
+            assert_eq!(
+                parse_chunks(&chunks, ParseOptions::UNLIMITED).err(),
+                MediaType::parse(input).err(),
+                "{input:?}"
+            );
+        }
+
+        let long = "a".repeat(200);
+        assert_eq!(
+            parse_chunks(&[long.as_bytes()], ParseOptions::UNLIMITED),
+            Err(MediaTypeError::InvalidTypeName { position: 127 })
+        );
+        assert_eq!(
+            parse_chunks(&[b"text/", b"\xe3\x81", b"\x82"], ParseOptions::UNLIMITED),
+            Err(MediaTypeError::MissingSubtype { position: 5 })
+        );
+        assert_eq!(
+            parse_chunks(&[b"text/plain; a=\"\xe3\x81"], ParseOptions::UNLIMITED),
+            Err(MediaTypeError::InvalidEncoding { position: 15 })
+        );
+    }
+
+    #[test]
+    fn options() {
+        let relaxed = ParseOptions {
+            relaxed: true,
+            ..ParseOptions::UNLIMITED
+        };
+        for input in [" \ttext/html;; charset = utf-8;", "  te/xt", "  t@xt/html"] {
+            let chunks: Vec<&[u8]> = input.as_bytes().chunks(1).collect();
+            assert_eq!(
+                parse_chunks(&chunks, relaxed).map(|media_type| media_type.to_string()),
+                MediaType::parse_relaxed(input).map(|media_type| media_type.to_string()),
+                "{input:?}"
+            );
+        }
+
+        let limited = ParseOptions {
+            max_length: 4,
+            ..ParseOptions::UNLIMITED
+        };
+        assert_eq!(
+            parse_chunks(&[b"text/plain"], limited),
+            Err(MediaTypeError::TooLarge { position: 4 })
+        );
+        assert_eq!(
+            parse_chunks(&[b"a/b", b"c"], limited).map(|media_type| media_type.to_string()),
+            Ok("a/bc".into())
+        );
+
+        let input = "\u{feff}\ttext/plain;\tcharset=utf-8";
+        let chunks: Vec<&[u8]> = input.as_bytes().chunks(1).collect();
+        assert_eq!(
+            parse_chunks(&chunks, ParseOptions::UNLIMITED),
+            input.parse::<MediaTypeBuf>()
+        );
+        assert_eq!(
+            parse_chunks(&[b"\xef\xbbtext/plain"], ParseOptions::UNLIMITED),
+            Err(MediaTypeError::InvalidEncoding { position: 0 })
+        );
+        assert_eq!(
+            parse_chunks(&[b"\xef"], ParseOptions::UNLIMITED),
+            Err(MediaTypeError::InvalidEncoding { position: 0 })
+        );
+        assert_eq!(
+            parse_chunks(&[b" \xef\xbb\xbftext/plain"], ParseOptions::UNLIMITED),
+            Err(MediaTypeError::InvalidTypeName { position: 1 })
+        );
+
+        let mut parser = MediaTypeParser::new();
+        parser.feed(b"\xff");
+        assert_eq!(
+            parser.error(),
+            Some(MediaTypeError::InvalidTypeName { position: 0 })
+        );
+        parser.feed(b"text/plain");
+        assert_eq!(
+            parser.finish(),
+            Err(MediaTypeError::InvalidTypeName { position: 0 })
+        );
+    }
+}
@@ -0,0 +1,218 @@
+use super::{error::*, media_type::*, media_type_buf::*, name::*, params::*, value::*};
+use alloc::{string::String, sync::Arc};
+use core::{
+    fmt,
+    hash::{Hash, Hasher},
+    ops::Deref,
+    str::FromStr,
+};
+
+/// A reference-counted, owned and immutable media type.
+///
+/// Cloning a `MediaTypeShared` only increments the reference count,
+/// so it can be cheaply shared across threads. All the methods of [`MediaTypeBuf`]
+/// are available through [`Deref`].
+///
+/// ```
+/// use mediatype::{names::*, values::*, MediaTypeShared, ReadParams};
+///
+/// let text_plain: MediaTypeShared = "text/plain; charset=UTF-8".parse().unwrap();
+/// let cloned = text_plain.clone();
+/// assert_eq!(cloned.get_param(CHARSET), Some(UTF_8));
+/// assert_eq!(cloned.as_str().as_ptr(), text_plain.as_str().as_ptr());
+/// ```
+#[derive(Clone)]
+pub struct MediaTypeShared(Arc<MediaTypeBuf>);
+
+impl MediaTypeShared {
+    /// Constructs a `MediaTypeShared` from [`String`].
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the string fails to be parsed.
+    ///
+    /// [`String`]: https://doc.rust-lang.org/std/string/struct.String.html
+    pub fn from_string(s: String) -> Result<Self, MediaTypeError> {
+        MediaTypeBuf::from_string(s).map(Self::from)
+    }
+}
+
+impl Deref for MediaTypeShared {
+    type Target = MediaTypeBuf;
+
+    fn deref(&self) -> &MediaTypeBuf {
+        &self.0
+    }
+}
+
+impl AsRef<MediaTypeBuf> for MediaTypeShared {
+    fn as_ref(&self) -> &MediaTypeBuf {
+        &self.0
+    }
+}
+
+impl AsRef<str> for MediaTypeShared {
+    fn as_ref(&self) -> &str {
+        self.0.as_str()
+    }
+}
+
+impl ReadParams for MediaTypeShared {
+    fn params(&self) -> Params<'_> {
+        self.0.params()
+    }
+
+    fn get_param(&self, name: Name) -> Option<Value<'_>> {
+        self.0.get_param(name)
+    }
+
+    fn has_param(&self, name: Name) -> bool {
+        self.0.has_param(name)
+    }
+}
+
+impl FromStr for MediaTypeShared {
+    type Err = MediaTypeError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        MediaTypeBuf::from_str(s).map(Self::from)
+    }
+}
+
+impl From<MediaTypeBuf> for MediaTypeShared {
+    fn from(t: MediaTypeBuf) -> Self {
+        Self(Arc::new(t))
+    }
+}
+
+impl From<MediaType<'_>> for MediaTypeShared {
+    fn from(t: MediaType) -> Self {
+        MediaTypeBuf::from(t).into()
+    }
+}
+
+impl From<&MediaType<'_>> for MediaTypeShared {
+    fn from(t: &MediaType) -> Self {
+        MediaTypeBuf::from(t).into()
+    }
+}
+
+impl PartialEq for MediaTypeShared {
+    fn eq(&self, other: &Self) -> bool {
+        Arc::ptr_eq(&self.0, &other.0) || *self.0 == *other.0
+    }
+}
+
+impl Eq for MediaTypeShared {}
+
+impl PartialEq<MediaTypeBuf> for MediaTypeShared {
+    fn eq(&self, other: &MediaTypeBuf) -> bool {
+        *self.0 == *other
+    }
+}
+
+impl PartialEq<MediaType<'_>> for MediaTypeShared {
+    fn eq(&self, other: &MediaType) -> bool {
+        *self.0 == *other
+    }
+}
+
+impl PartialEq<str> for MediaTypeShared {
+    fn eq(&self, other: &str) -> bool {
+        self.0.matches_str(other)
+    }
+}
+
+impl PartialEq<&str> for MediaTypeShared {
+    fn eq(&self, other: &&str) -> bool {
+        self == *other
+    }
+}
+
+impl fmt::Debug for MediaTypeShared {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        debug_fields(
+            f,
+            "MediaTypeShared",
+            self.ty(),
+            self.subty(),
+            self.suffix(),
+            self.params(),
+        )
+    }
+}
+
+impl fmt::Display for MediaTypeShared {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        fmt::Display::fmt(&*self.0, f)
+    }
+}
+
+impl Hash for MediaTypeShared {
+    fn hash<H: Hasher>(&self, state: &mut H) {
+        self.0.hash(state);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{names::*, values::*};
+    use std::collections::hash_map::DefaultHasher;
+
+    fn calculate_hash<T: Hash>(t: &T) -> u64 {
+        let mut s = DefaultHasher::new();
+        t.hash(&mut s);
+        s.finish()
+    }
+
+    #[test]
+    fn clone() {
+        let media_type = MediaTypeShared::from_str("image/svg+xml; charset=UTF-8").unwrap();
+        let cloned = media_type.clone();
+        assert!(Arc::ptr_eq(&media_type.0, &cloned.0));
+        assert_eq!(cloned.ty(), IMAGE);
+        assert_eq!(cloned.suffix(), Some(XML));
+        assert_eq!(cloned.get_param(CHARSET), Some(UTF_8));
+        assert_eq!(cloned.to_string(), "image/svg+xml; charset=UTF-8");
+        assert_eq!(format!("{:#}", cloned), "image/svg+xml");
+    }
+
+    #[test]
+    fn cmp() {
+        let media_type = MediaTypeShared::from_str("TEXT/PLAIN; charset=UTF-8").unwrap();
+        let other = MediaTypeShared::from(MediaType::from_parts(
+            TEXT,
+            PLAIN,
+            None,
+            &[(CHARSET, UTF_8)],
+        ));
+        assert_eq!(media_type, other);
+        assert_eq!(calculate_hash(&media_type), calculate_hash(&other));
+        assert_eq!(
+            calculate_hash(&media_type),
+            calculate_hash(&MediaTypeBuf::from_str("text/plain; charset=UTF-8").unwrap())
+        );
+        assert_eq!(
+            media_type,
+            MediaType::parse("text/plain; charset=UTF-8").unwrap()
+        );
+        assert_eq!(media_type, "text/plain; charset=\"UTF-8\"");
+        assert_ne!(media_type, "text/plain");
+        assert!(MediaTypeShared::from_string("text".into()).is_err());
+    }
+
+    #[test]
+    fn send_sync() {
+        fn assert_send_sync<T: Send + Sync>() {}
+        assert_send_sync::<MediaTypeShared>();
+    }
+
+    #[test]
+    fn debug() {
+        assert_eq!(
+            format!("{:?}", MediaTypeShared::from_str("text/plain").unwrap()),
+            r#"MediaTypeShared { ty: "text", subty: "plain", suffix: None, params: [] }"#
+        );
+    }
+}
@@ -0,0 +1,70 @@
+#![cfg(feature = "mime")]
+
+use super::{error::*, media_type::*};
+use alloc::string::ToString;
+use mime::{FromStrError, Mime};
+
+#[cfg_attr(docsrs, doc(cfg(feature = "mime")))]
+impl<'a> TryFrom<&'a Mime> for MediaType<'a> {
+    type Error = MediaTypeError;
+
+    fn try_from(value: &'a Mime) -> Result<Self, Self::Error> {
+        Self::parse(value.as_ref())
+    }
+}
+
+#[cfg_attr(docsrs, doc(cfg(feature = "mime")))]
+impl<'a> TryFrom<MediaType<'a>> for Mime {
+    type Error = FromStrError;
+
+    fn try_from(value: MediaType<'a>) -> Result<Self, Self::Error> {
+        value.to_string().parse()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{Name, ReadParams};
+
+    #[test]
+    fn from_mime() {
+        let mime: Mime = "image/svg+xml; foo=Bar".parse().unwrap();
+        assert_eq!(
+            MediaType::try_from(&mime),
+            MediaType::parse("image/svg+xml; foo=Bar")
+        );
+        assert_eq!(
+            MediaType::try_from(&mime::TEXT_PLAIN_UTF_8),
+            MediaType::parse("text/plain; charset=utf-8")
+        );
+
+        let mime: Mime = "text/plain; title=\"Hello, world!\"".parse().unwrap();
+        let media_type = MediaType::try_from(&mime).unwrap();
+        assert_eq!(
+            media_type.get_param(Name::new("title").unwrap()).unwrap(),
+            "Hello, world!"
+        );
+
+        let mime: Mime = "text/pl~in".parse().unwrap();
+        assert_eq!(
+            MediaType::try_from(&mime),
+            Err(MediaTypeError::TrailingData { position: 7 })
+        );
+    }
+
+    #[test]
+    fn to_mime() {
+        let media_type = MediaType::parse("Text/HTML;charset=utf-8").unwrap();
+        let mime = Mime::try_from(media_type).unwrap();
+        assert_eq!(mime, mime::TEXT_HTML_UTF_8);
+
+        let media_type = MediaType::parse("image/svg+xml; a=\"b c\"").unwrap();
+        let mime = Mime::try_from(media_type).unwrap();
+        assert_eq!(mime.suffix(), Some(mime::XML));
+        assert_eq!(mime.get_param("a").unwrap(), "b c");
+
+        let media_type = MediaType::parse("text/plain; a=\"\\\"\"").unwrap();
+        assert!(Mime::try_from(media_type).is_err());
+    }
+}
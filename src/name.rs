@@ -1,9 +1,10 @@
-use super::parse::*;
-use std::{
-    borrow::Cow,
+use super::{error::*, parse::*};
+use alloc::{borrow::Cow, string::String};
+use core::{
     cmp::Ordering,
     fmt,
     hash::{Hash, Hasher},
+    ops::Deref,
 };
 
 /// A media-type name.
@@ -31,10 +32,77 @@ impl<'a> Name<'a> {
 
     /// Returns the underlying string.
     #[must_use]
-    pub const fn as_str(&self) -> &str {
+    pub const fn as_str(&self) -> &'a str {
         self.0
     }
 
+    /// Returns the byte length of the name.
+    #[must_use]
+    pub const fn len(&self) -> usize {
+        self.0.len()
+    }
+
+    /// Returns `true` if the name is empty.
+    ///
+    /// A valid name is never empty.
+    #[must_use]
+    pub const fn is_empty(&self) -> bool {
+        self.0.is_empty()
+    }
+
+    /// Returns `true` if the name starts with `prefix`, ignoring ASCII case.
+    ///
+    /// [`str::starts_with`] through `Deref` compares case-sensitively.
+    ///
+    /// ```
+    /// # use mediatype::MediaType;
+    /// let media_type = MediaType::parse("application/VND.ms-excel").unwrap();
+    /// assert!(media_type.subty.starts_with_ignore_case("vnd."));
+    /// ```
+    #[must_use]
+    pub fn starts_with_ignore_case(&self, prefix: &str) -> bool {
+        self.0
+            .as_bytes()
+            .get(..prefix.len())
+            .is_some_and(|s| s.eq_ignore_ascii_case(prefix.as_bytes()))
+    }
+
+    /// Returns `true` if the name ends with `suffix`, ignoring ASCII case.
+    ///
+    /// ```
+    /// # use mediatype::MediaType;
+    /// let media_type = MediaType::parse("image/vnd.Microsoft.ICON").unwrap();
+    /// assert!(media_type.subty.ends_with_ignore_case(".icon"));
+    /// ```
+    #[must_use]
+    pub fn ends_with_ignore_case(&self, suffix: &str) -> bool {
+        self.0
+            .len()
+            .checked_sub(suffix.len())
+            .is_some_and(|start| self.0.as_bytes()[start..].eq_ignore_ascii_case(suffix.as_bytes()))
+    }
+
+    /// Splits the name at the last `+` into a base and a suffix.
+    ///
+    /// Returns the name itself and `None` if there is no `+`,
+    /// or if either side would not be a valid name.
+    ///
+    /// ```
+    /// # use mediatype::{names::*, Name};
+    /// let subty = Name::new("svg+xml").unwrap();
+    /// assert_eq!(subty.split_suffix(), (SVG, Some(XML)));
+    /// assert_eq!(SVG.split_suffix(), (SVG, None));
+    /// ```
+    #[must_use]
+    pub fn split_suffix(self) -> (Self, Option<Self>) {
+        match self.0.rsplit_once('+') {
+            Some((base, suffix)) if is_restricted_name(base) && is_restricted_name(suffix) => {
+                (Self(base), Some(Self(suffix)))
+            }
+            _ => (self, None),
+        }
+    }
+
     /// The maximum byte length of a name.
     pub const MAX_LENGTH: usize = 127;
 
@@ -50,6 +118,35 @@ impl<'a> fmt::Display for Name<'a> {
     }
 }
 
+/// Constructs a `Name` in the same way as [`Name::new`].
+///
+/// ```
+/// # use mediatype::{names::*, MediaTypeError, Name};
+/// let charset: Name = "charset".try_into().unwrap();
+/// assert_eq!(charset, CHARSET);
+/// assert_eq!(
+///     Name::try_from("char set"),
+///     Err(MediaTypeError::InvalidParamName { position: 4 })
+/// );
+/// ```
+impl<'a> TryFrom<&'a str> for Name<'a> {
+    type Error = MediaTypeError;
+
+    fn try_from(s: &'a str) -> Result<Self, Self::Error> {
+        Self::new(s).ok_or_else(|| MediaTypeError::InvalidParamName {
+            position: invalid_name_position(s).unwrap_or(0),
+        })
+    }
+}
+
+impl<'a> Deref for Name<'a> {
+    type Target = str;
+
+    fn deref(&self) -> &str {
+        self.0
+    }
+}
+
 impl<'a> AsRef<str> for Name<'a> {
     fn as_ref(&self) -> &str {
         self.0
@@ -81,7 +178,10 @@ impl<'a> Ord for Name<'a> {
 
 impl<'a> Hash for Name<'a> {
     fn hash<H: Hasher>(&self, state: &mut H) {
-        self.0.to_ascii_lowercase().hash(state);
+        for b in self.0.bytes() {
+            state.write_u8(b.to_ascii_lowercase());
+        }
+        state.write_u8(0xff);
     }
 }
 
@@ -161,3 +261,100 @@ impl<'a> PartialOrd<&Cow<'_, str>> for Name<'a> {
         self.partial_cmp(other.as_ref())
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn new() {
+        for s in [
+            "text",
+            "3gpp",
+            "vnd.ms-excel",
+            "svg+xml",
+            "x_y",
+            "*",
+            "a".repeat(127).as_str(),
+        ] {
+            assert_eq!(Name::new(s).map(|name| name.as_str()), Some(s), "{s:?}");
+        }
+        for s in [
+            "",
+            "a/b",
+            "a b",
+            " a",
+            "a;b",
+            "a=b",
+            "\"a\"",
+            "a|b",
+            "a~b",
+            "a(b)",
+            "-a",
+            ".a",
+            "+xml",
+            "\u{e9}",
+            "a".repeat(128).as_str(),
+        ] {
+            assert_eq!(Name::new(s), None, "{s:?}");
+        }
+    }
+
+    #[test]
+    fn try_from() {
+        assert_eq!(Name::try_from("vnd.ms-excel"), Ok(Name("vnd.ms-excel")));
+        assert_eq!(
+            Name::try_from(""),
+            Err(MediaTypeError::InvalidParamName { position: 0 })
+        );
+        assert_eq!(
+            Name::try_from("-abc"),
+            Err(MediaTypeError::InvalidParamName { position: 0 })
+        );
+        assert_eq!(
+            Name::try_from("a".repeat(200).as_str()),
+            Err(MediaTypeError::InvalidParamName { position: 127 })
+        );
+    }
+
+    #[test]
+    fn as_str() {
+        let name = Name::new("vnd.MS-Excel").unwrap();
+        let s: &'static str = name.as_str();
+        assert_eq!(s, "vnd.MS-Excel");
+        assert_eq!(name.len(), 12);
+        assert!(!name.is_empty());
+        assert_eq!(name.to_ascii_lowercase(), "vnd.ms-excel");
+        assert!(name.contains("MS"));
+    }
+
+    #[test]
+    fn split_suffix() {
+        let split = |s| {
+            let (base, suffix) = Name::new(s).unwrap().split_suffix();
+            (base.as_str(), suffix.map(|suffix| suffix.as_str()))
+        };
+        assert_eq!(split("svg+xml"), ("svg", Some("xml")));
+        assert_eq!(split("vnd.foo+xml+zip"), ("vnd.foo+xml", Some("zip")));
+        assert_eq!(split("svg"), ("svg", None));
+        assert_eq!(split("svg+"), ("svg+", None));
+        assert_eq!(split("a++b"), ("a+", Some("b")));
+        assert_eq!(split("a+-b"), ("a+-b", None));
+    }
+
+    #[test]
+    fn starts_with_ignore_case() {
+        let name = Name::new("vnd.MS-Excel").unwrap();
+        assert!(name.starts_with_ignore_case(""));
+        assert!(name.starts_with_ignore_case("vnd."));
+        assert!(name.starts_with_ignore_case("VND.ms"));
+        assert!(!name.starts_with_ignore_case("prs."));
+        assert!(!name.starts_with_ignore_case("vnd.ms-excel2"));
+        assert!(name.ends_with_ignore_case("excel"));
+        assert!(name.ends_with_ignore_case("VND.ms-EXCEL"));
+        assert!(!name.ends_with_ignore_case("word"));
+        assert!(!name.ends_with_ignore_case("x-vnd.ms-excel"));
+        assert!(!name.starts_with("VND."));
+        assert!(!name.ends_with("EXCEL"));
+    }
+}
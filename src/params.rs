@@ -1,10 +1,22 @@
-use super::{name::*, parse::*, value::*};
+use super::{
+    consts::names::{CHARSET, Q},
+    name::*,
+    parse::*,
+    value::*,
+};
+use alloc::collections::BTreeMap;
+use core::{
+    fmt,
+    hash::{Hash, Hasher},
+    iter::Rev,
+};
 
 /// An iterator over the parameters.
-#[derive(Debug)]
+#[derive(Debug, Clone)]
 pub struct Params<'a> {
     source: ParamsSource<'a>,
     index: usize,
+    end: usize,
 }
 
 impl<'a> Params<'a> {
@@ -12,6 +24,7 @@ impl<'a> Params<'a> {
         Self {
             source: ParamsSource::Slice(s),
             index: 0,
+            end: s.len(),
         }
     }
 
@@ -19,11 +32,85 @@ impl<'a> Params<'a> {
         Self {
             source: ParamsSource::Indices(s, i),
             index: 0,
+            end: i.params().len(),
+        }
+    }
+
+    /// Returns the number of the remaining parameters.
+    #[must_use]
+    pub const fn len(&self) -> usize {
+        self.end - self.index
+    }
+
+    /// Returns `true` if there are no remaining parameters.
+    #[must_use]
+    pub const fn is_empty(&self) -> bool {
+        self.len() == 0
+    }
+
+    /// Returns the parameter at `index` of the remaining parameters without advancing the iterator.
+    ///
+    /// ```
+    /// # use mediatype::{names::*, values::*, MediaType, ReadParams};
+    /// let media_type = MediaType::parse("text/plain; charset=UTF-8; format=flowed").unwrap();
+    /// let mut params = media_type.params();
+    /// assert_eq!(params.len(), 2);
+    /// assert_eq!(params.get(1), Some((FORMAT, FLOWED)));
+    ///
+    /// params.next();
+    /// assert_eq!(params.get(0), Some((FORMAT, FLOWED)));
+    /// assert_eq!(params.get(1), None);
+    /// assert_eq!(params.len(), 1);
+    /// ```
+    #[must_use]
+    pub fn get(&self, index: usize) -> Option<(Name<'a>, Value<'a>)> {
+        let index = self.index.checked_add(index).filter(|&i| i < self.end)?;
+        match self.source {
+            ParamsSource::Slice(s) => s.get(index).copied(),
+            ParamsSource::Indices(s, i) => i.params().get(index).map(|param| {
+                (
+                    Name::new_unchecked(&s[param[0]..param[1]]),
+                    Value::from_param(s, param),
+                )
+            }),
+        }
+    }
+
+    /// Returns an iterator over the parameters ordered by name.
+    ///
+    /// Names are compared case-insensitively. If the same name appears more than once,
+    /// only the last value is yielded.
+    ///
+    /// ```
+    /// # use mediatype::{names::*, MediaType, ReadParams, Value};
+    /// let media_type = MediaType::parse("text/plain; format=flowed; charset=UTF-8").unwrap();
+    /// let mut params = media_type.params().sorted();
+    /// assert_eq!(params.next(), Some((CHARSET, Value::new("UTF-8").unwrap())));
+    /// assert_eq!(params.next(), Some((FORMAT, Value::new("flowed").unwrap())));
+    /// assert_eq!(params.next(), None);
+    /// ```
+    pub fn sorted(
+        self,
+    ) -> impl DoubleEndedIterator<Item = (Name<'a>, Value<'a>)> + ExactSizeIterator {
+        self.collect::<BTreeMap<_, _>>().into_iter()
+    }
+
+    /// Compares the parameters as sets, in the same way as [`sorted`](Self::sorted).
+    pub(crate) fn set_eq(self, other: Params) -> bool {
+        self.sorted().eq(other.sorted())
+    }
+
+    /// Hashes the parameters consistently with [`set_eq`](Self::set_eq).
+    pub(crate) fn set_hash<H: Hasher>(self, state: &mut H) {
+        let params = self.sorted();
+        state.write_usize(params.len());
+        for param in params {
+            param.hash(state);
         }
     }
 }
 
-#[derive(Debug)]
+#[derive(Debug, Clone)]
 enum ParamsSource<'a> {
     Slice(&'a [(Name<'a>, Value<'a>)]),
     Indices(&'a str, &'a Indices),
@@ -33,56 +120,230 @@ impl<'a> Iterator for Params<'a> {
     type Item = (Name<'a>, Value<'a>);
 
     fn next(&mut self) -> Option<Self::Item> {
-        let index = self.index;
-        match self.source {
-            ParamsSource::Slice(s) => {
-                if index >= s.len() {
-                    None
-                } else {
-                    self.index += 1;
-                    Some((s[index].0, s[index].1))
-                }
-            }
-            ParamsSource::Indices(s, i) => {
-                if index >= i.params().len() {
-                    None
-                } else {
-                    self.index += 1;
-                    let param = i.params()[index];
-                    Some((
-                        Name::new_unchecked(&s[param[0]..param[1]]),
-                        Value::new_unchecked(&s[param[2]..param[3]]),
-                    ))
-                }
-            }
-        }
+        let param = self.get(0)?;
+        self.index += 1;
+        Some(param)
     }
 
     fn size_hint(&self) -> (usize, Option<usize>) {
-        let len = match self.source {
-            ParamsSource::Slice(s) => s.len(),
-            ParamsSource::Indices(_, i) => i.params().len(),
-        };
+        let len = self.len();
         (len, Some(len))
     }
 }
 
+impl<'a> DoubleEndedIterator for Params<'a> {
+    fn next_back(&mut self) -> Option<Self::Item> {
+        let param = self.get(self.len().checked_sub(1)?)?;
+        self.end -= 1;
+        Some(param)
+    }
+}
+
+impl<'a> ExactSizeIterator for Params<'a> {}
+
+/// Writes `; name=value`, or `; name` if the value is [`Value::ABSENT`].
+pub(crate) fn write_param(
+    w: &mut impl fmt::Write,
+    name: impl fmt::Display,
+    value: Value,
+) -> fmt::Result {
+    if value.is_absent() {
+        write!(w, "; {name}")
+    } else {
+        write!(w, "; {name}={value}")
+    }
+}
+
+/// An iterator over the parameter names.
+///
+/// This is returned by [`ReadParams::param_names`].
+#[derive(Debug, Clone)]
+pub struct ParamNames<'a>(Params<'a>);
+
+impl<'a> Iterator for ParamNames<'a> {
+    type Item = Name<'a>;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        self.0.next().map(|(name, _)| name)
+    }
+
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        self.0.size_hint()
+    }
+}
+
+impl<'a> ExactSizeIterator for ParamNames<'a> {}
+
 /// A trait for getting parameter values.
 pub trait ReadParams {
     /// Returns the parameters.
-    fn params(&self) -> Params;
+    fn params(&self) -> Params<'_>;
 
     /// Gets the parameter value by its name.
     ///
     /// If the same name appears more than once, returns the last value.
-    fn get_param(&self, name: Name) -> Option<Value>;
+    /// Note that the [WHATWG MIME Sniffing] algorithm keeps the first one instead;
+    /// use [`MediaTypeBuf::parse_whatwg`](crate::MediaTypeBuf::parse_whatwg) for that behavior.
+    ///
+    /// [WHATWG MIME Sniffing]: https://mimesniff.spec.whatwg.org/#parsing-a-mime-type
+    fn get_param(&self, name: Name) -> Option<Value<'_>>;
+
+    /// Returns the number of the parameters.
+    ///
+    /// Parameters with the same name are counted separately.
+    ///
+    /// ```
+    /// # use mediatype::{MediaType, ReadParams};
+    /// let media_type = MediaType::parse("text/plain; charset=UTF-8; format=fixed").unwrap();
+    /// assert_eq!(media_type.params_len(), 2);
+    /// ```
+    fn params_len(&self) -> usize {
+        self.params().len()
+    }
+
+    /// Returns an iterator over the parameter names in order.
+    ///
+    /// Parameters with the same name are yielded separately.
+    ///
+    /// ```
+    /// # use mediatype::{names::*, MediaType, ReadParams};
+    /// let media_type = MediaType::parse("text/plain; charset=UTF-8; x-unknown=1").unwrap();
+    /// let unknown = media_type
+    ///     .param_names()
+    ///     .filter(|name| ![CHARSET, FORMAT].contains(name))
+    ///     .collect::<Vec<_>>();
+    /// assert_eq!(unknown, ["x-unknown"]);
+    /// ```
+    fn param_names(&self) -> ParamNames<'_> {
+        ParamNames(self.params())
+    }
+
+    /// Returns an iterator over the parameters in reverse order.
+    ///
+    /// This is the order in which [`get_param`](Self::get_param) resolves duplicates:
+    /// the first parameter yielded for a name is the one `get_param` returns.
+    ///
+    /// ```
+    /// # use mediatype::{names::*, values::*, MediaType, ReadParams};
+    /// let media_type = MediaType::parse("text/plain; charset=US-ASCII; charset=UTF-8").unwrap();
+    /// let (_, first) = media_type.params_rev().find(|(name, _)| *name == CHARSET).unwrap();
+    /// assert_eq!(first, UTF_8);
+    /// assert_eq!(media_type.get_param(CHARSET), Some(first));
+    /// ```
+    fn params_rev(&self) -> Rev<Params<'_>> {
+        self.params().rev()
+    }
+
+    /// Collects the parameters into a map.
+    ///
+    /// Names are compared case-insensitively. If the same name appears more than once,
+    /// only the last one is kept in the same way as [`get_param`](Self::get_param).
+    ///
+    /// ```
+    /// # use mediatype::{names::*, values::*, MediaType, ReadParams};
+    /// let media_type = MediaType::parse("text/plain; charset=US-ASCII; format=fixed; CHARSET=UTF-8").unwrap();
+    /// let params = media_type.params_as_map();
+    /// assert_eq!(params.len(), 2);
+    /// assert_eq!(params[&CHARSET], UTF_8);
+    /// assert_eq!(params.get(&FORMAT), Some(&FIXED));
+    /// ```
+    fn params_as_map(&self) -> BTreeMap<Name<'_>, Value<'_>> {
+        self.params().collect()
+    }
+
+    /// Checks whether any parameters exist.
+    ///
+    /// ```
+    /// # use mediatype::{MediaType, ReadParams};
+    /// assert!(MediaType::parse("text/plain; charset=UTF-8").unwrap().has_params());
+    /// assert!(!MediaType::parse("text/plain").unwrap().has_params());
+    /// ```
+    fn has_params(&self) -> bool {
+        self.params_len() > 0
+    }
+
+    /// Checks whether a parameter with the name exists.
+    ///
+    /// ```
+    /// # use mediatype::{names::*, MediaType, ReadParams};
+    /// let text_plain = MediaType::parse("text/plain; CHARSET=UTF-8").unwrap();
+    /// assert!(text_plain.has_param(CHARSET));
+    /// assert!(!text_plain.has_param(FORMAT));
+    /// ```
+    fn has_param(&self, name: Name) -> bool {
+        self.params().any(|param| name == param.0)
+    }
+
+    /// Checks whether the parameter with the name has the value.
+    ///
+    /// The name is compared case-insensitively and the value is compared case-sensitively
+    /// by its unquoted content. Parameter values are case-sensitive unless the parameter
+    /// defines otherwise. ([RFC 9110](https://www.rfc-editor.org/rfc/rfc9110#section-5.6.6))
+    ///
+    /// ```
+    /// # use mediatype::{names::*, values::*, MediaType, ReadParams};
+    /// let text_plain = MediaType::parse("text/plain; FORMAT=\"flowed\"").unwrap();
+    /// assert!(text_plain.param_eq(FORMAT, FLOWED));
+    /// assert!(!text_plain.param_eq(FORMAT, FIXED));
+    /// ```
+    fn param_eq(&self, name: Name, value: Value) -> bool {
+        self.get_param(name) == Some(value)
+    }
+
+    /// Checks whether the parameter with the name has the value, ignoring ASCII case of the value.
+    ///
+    /// Use this for parameters with case-insensitive values like `charset`.
+    /// ([RFC 2046](https://www.rfc-editor.org/rfc/rfc2046#section-4.1.2))
+    ///
+    /// ```
+    /// # use mediatype::{names::*, values::*, MediaType, ReadParams};
+    /// let text_plain = MediaType::parse("text/plain; charset=utf-8").unwrap();
+    /// assert!(text_plain.param_eq_ignore_case(CHARSET, UTF_8));
+    /// assert!(!text_plain.param_eq(CHARSET, UTF_8));
+    /// ```
+    fn param_eq_ignore_case(&self, name: Name, value: Value) -> bool {
+        self.get_param(name)
+            .is_some_and(|param| param.unquoted().eq_ignore_ascii_case(&value.unquoted()))
+    }
+
+    /// Returns the `charset` parameter value.
+    ///
+    /// ```
+    /// # use mediatype::{values::*, MediaType, ReadParams};
+    /// let text_plain = MediaType::parse("text/plain; charset=UTF-8").unwrap();
+    /// assert_eq!(text_plain.charset(), Some(UTF_8));
+    /// ```
+    fn charset(&self) -> Option<Value<'_>> {
+        self.get_param(CHARSET)
+    }
+
+    /// Returns the quality value (`q` parameter) in the range `[0.0, 1.0]`.
+    ///
+    /// Out-of-range values are clamped. If the parameter is absent or malformed,
+    /// including having more than three decimal digits, returns `1.0`.
+    /// ([RFC 7231](https://www.rfc-editor.org/rfc/rfc7231#section-5.3.1))
+    ///
+    /// ```
+    /// # use mediatype::{MediaType, ReadParams};
+    /// assert_eq!(MediaType::parse("text/html; q=0.8").unwrap().quality(), 0.8);
+    /// assert_eq!(MediaType::parse("text/html").unwrap().quality(), 1.0);
+    /// assert_eq!(MediaType::parse("text/html; q=0.1234").unwrap().quality(), 1.0);
+    /// ```
+    fn quality(&self) -> f32 {
+        self.get_param(Q)
+            .and_then(|q| parse_quality(&q.unquoted()))
+            .unwrap_or(1.0)
+    }
 }
 
 /// A trait for mutating parameter values.
 pub trait WriteParams<'a>: ReadParams {
     /// Sets a parameter value.
     ///
-    /// If the parameters with the name already exist, they will be removed.
+    /// If the parameters with the name already exist, they will be removed,
+    /// and the new parameter is appended to the end. Use
+    /// [`MediaType::set_param_keep_order`](crate::MediaType::set_param_keep_order)
+    /// to keep the position instead.
     ///
     /// ```
     /// # use mediatype::{names::*, values::*, MediaType, WriteParams};
@@ -98,6 +359,40 @@ pub trait WriteParams<'a>: ReadParams {
     /// ```
     fn set_param<'n: 'a, 'v: 'a>(&mut self, name: Name<'n>, value: Value<'v>);
 
+    /// Sets the `charset` parameter value.
+    ///
+    /// ```
+    /// # use mediatype::{names::*, values::*, MediaType, ReadParams, WriteParams};
+    /// let mut text_plain = MediaType::new(TEXT, PLAIN);
+    /// text_plain.set_charset(UTF_8);
+    /// assert_eq!(text_plain.to_string(), "text/plain; charset=UTF-8");
+    /// ```
+    fn set_charset<'v: 'a>(&mut self, value: Value<'v>) {
+        self.set_param(CHARSET, value);
+    }
+
+    /// Sets multiple parameters at once.
+    ///
+    /// Each parameter is set in the same way as [`set_param`](Self::set_param):
+    /// existing parameters with the same name are removed and the new one is appended.
+    /// The default implementation simply calls `set_param` for each parameter.
+    ///
+    /// ```
+    /// # use mediatype::{names::*, values::*, MediaType, WriteParams};
+    /// let mut text_plain = MediaType::parse("text/plain; charset=US-ASCII; format=fixed").unwrap();
+    /// text_plain.extend_params([(CHARSET, UTF_8), (FORMAT, FLOWED)]);
+    ///
+    /// assert_eq!(
+    ///     text_plain.to_string(),
+    ///     "text/plain; charset=UTF-8; format=flowed"
+    /// );
+    /// ```
+    fn extend_params<I: IntoIterator<Item = (Name<'a>, Value<'a>)>>(&mut self, iter: I) {
+        for (name, value) in iter {
+            self.set_param(name, value);
+        }
+    }
+
     /// Removes all parameters with the name.
     fn remove_params(&mut self, name: Name);
 
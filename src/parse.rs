@@ -1,5 +1,6 @@
-use super::{error::*, name::*};
-use std::{num::NonZeroU8, ops::Range};
+use super::{error::*, name::*, parse_options::*};
+use alloc::{boxed::Box, vec::Vec};
+use core::{num::NonZeroU8, ops::Range};
 
 #[derive(Debug, Clone)]
 pub struct Indices {
@@ -34,22 +35,76 @@ impl Indices {
         &self.params
     }
 
+    /// Constructs `Indices` of `ty/subty+suffix` followed by the parameters
+    /// at the given ranges. The names must be valid.
+    pub fn from_valid_parts(
+        ty: usize,
+        subty: usize,
+        suffix: usize,
+        params: Box<[[usize; 4]]>,
+    ) -> Self {
+        Self {
+            ty: NonZeroU8::new(ty.try_into().unwrap()).unwrap(),
+            subty: NonZeroU8::new(subty.try_into().unwrap()).unwrap(),
+            suffix: suffix.try_into().unwrap(),
+            params,
+        }
+    }
+
+    pub fn push_param(&mut self, param: [usize; 4]) {
+        let mut params = core::mem::take(&mut self.params).into_vec();
+        params.push(param);
+        self.params = params.into_boxed_slice();
+    }
+
     pub fn parse(s: &str) -> Result<(Self, usize), MediaTypeError> {
+        Self::parse_with(s, &ParseOptions::UNLIMITED)
+    }
+
+    pub fn parse_with(s: &str, options: &ParseOptions) -> Result<(Self, usize), MediaTypeError> {
+        let mut params = Vec::new();
+        let (mut indices, len) = Self::parse_each(s, options, |param| params.push(param))?;
+        indices.params = params.into_boxed_slice();
+        Ok((indices, len))
+    }
+
+    /// Parses `s` and passes the ranges of each parameter to `push`
+    /// instead of collecting them. The returned `Indices` has no parameters.
+    pub fn parse_each(
+        s: &str,
+        options: &ParseOptions,
+        push: impl FnMut([usize; 4]),
+    ) -> Result<(Self, usize), MediaTypeError> {
+        if s.len() > options.max_length {
+            return Err(MediaTypeError::TooLarge {
+                position: options.max_length,
+            });
+        }
+
         // ty.len() + '/' + subty.len() + '+' + suffix.len()
         const MAX_ESSENCE_LENGTH: usize = Name::MAX_LENGTH * 3 + 2;
 
-        let (ty, right) = match s[..MAX_ESSENCE_LENGTH.min(s.len())].split_once('/') {
-            Some(pair) => pair,
-            _ => return Err(MediaTypeError::InvalidTypeName),
+        let slash = s.bytes().take(MAX_ESSENCE_LENGTH).position(|b| b == b'/');
+        let (ty, right) = match slash {
+            Some(slash) => (&s[..slash], &s[slash + 1..]),
+            _ if s.is_empty() => return Err(MediaTypeError::EmptyType { position: 0 }),
+            _ => {
+                return Err(match invalid_name_position(s) {
+                    Some(position) => MediaTypeError::InvalidTypeName { position },
+                    None => MediaTypeError::MissingSlash { position: s.len() },
+                });
+            }
         };
 
-        if !is_restricted_name(ty) {
-            return Err(MediaTypeError::InvalidTypeName);
+        if ty.is_empty() {
+            return Err(MediaTypeError::EmptyType { position: 0 });
+        }
+        if let Some(position) = invalid_name_position(ty) {
+            return Err(MediaTypeError::InvalidTypeName { position });
         }
 
-        let suffix_end = right
-            .find(|c: char| !is_restricted_char(c))
-            .unwrap_or(right.len());
+        let subty_start = ty.len() + 1;
+        let suffix_end = restricted_len(right);
         let suffix_start = right[..suffix_end].rfind('+');
 
         let (subty, suffix) = suffix_start.map_or_else(
@@ -57,35 +112,42 @@ impl Indices {
             |suffix_start| (&right[..suffix_start], &right[suffix_start + 1..suffix_end]),
         );
 
-        if !is_restricted_name(subty) {
-            return Err(MediaTypeError::InvalidSubtypeName);
+        if subty.is_empty() {
+            return Err(MediaTypeError::MissingSubtype {
+                position: subty_start,
+            });
         }
-
-        if !suffix.is_empty() && !is_restricted_name(&suffix[1..]) {
-            return Err(MediaTypeError::InvalidSuffix);
+        if let Some(position) = invalid_name_position(subty) {
+            return Err(MediaTypeError::InvalidSubtypeName {
+                position: subty_start + position,
+            });
         }
 
-        let sub_end = ty.len() + 1 + subty.len();
-        let params_start = sub_end
-            + if suffix.is_empty() {
-                0
-            } else {
-                suffix.len() + 1
-            };
-
-        let (mut params, params_len) = parse_params(&s[params_start..])?;
-        for elem in &mut params {
-            for v in elem.iter_mut() {
-                *v += params_start;
+        if let Some(suffix_start) = suffix_start {
+            if suffix.is_empty() {
+                return Err(MediaTypeError::EmptySuffix {
+                    position: subty_start + suffix_start + 1,
+                });
+            }
+            if let Some(position) = invalid_name_position(suffix) {
+                return Err(MediaTypeError::InvalidSuffix {
+                    position: subty_start + suffix_start + 1 + position,
+                });
             }
         }
 
+        let sub_end = ty.len() + 1 + subty.len();
+        let params_start = sub_end + suffix_start.map_or(0, |_| suffix.len() + 1);
+
+        let params_len = parse_params(&s[params_start..], options, params_start, push)
+            .map_err(|err| err.offset(params_start))?;
+
         Ok((
             Self {
                 ty: NonZeroU8::new(ty.len().try_into().unwrap()).unwrap(),
                 subty: NonZeroU8::new(subty.len().try_into().unwrap()).unwrap(),
                 suffix: suffix.len().try_into().unwrap(),
-                params: params.into_boxed_slice(),
+                params: Box::default(),
             },
             params_start + params_len,
         ))
@@ -94,7 +156,7 @@ impl Indices {
 
 #[cfg(test)]
 fn parse_to_string(s: &str) -> Result<String, MediaTypeError> {
-    use std::fmt::Write;
+    use core::fmt::Write;
 
     let mut out = String::new();
     let (indices, _) = Indices::parse(s)?;
@@ -117,105 +179,264 @@ fn parse_to_string(s: &str) -> Result<String, MediaTypeError> {
 }
 
 pub fn is_restricted_name(s: &str) -> bool {
-    s.len() <= Name::MAX_LENGTH
-        && s.starts_with(|c: char| c.is_ascii_alphanumeric() || c == '*')
-        && is_restricted_str(s)
+    invalid_name_position(s).is_none()
+}
+
+pub fn invalid_name_position(s: &str) -> Option<usize> {
+    if !s.starts_with(|c: char| c.is_ascii_alphanumeric() || c == '*') {
+        return Some(0);
+    }
+    let invalid_char = Some(restricted_len(s)).filter(|&len| len < s.len());
+    let too_long = Some(Name::MAX_LENGTH).filter(|_| s.len() > Name::MAX_LENGTH);
+    match (invalid_char, too_long) {
+        (Some(a), Some(b)) => Some(a.min(b)),
+        (a, b) => a.or(b),
+    }
+}
+
+pub fn invalid_value_position(s: &str) -> Option<usize> {
+    if let Some(quoted) = s.strip_prefix('\"') {
+        match parse_quoted_value(quoted) {
+            Ok(len) if len == quoted.len() => None,
+            Ok(len) => Some(len + 1),
+            Err(err) => err.position().map(|position| position + 1),
+        }
+    } else {
+        Some(restricted_len(s)).filter(|&len| len < s.len())
+    }
 }
 
 pub fn is_restricted_str(s: &str) -> bool {
-    s.chars().all(is_restricted_char)
+    restricted_len(s) == s.len()
 }
 
 pub fn is_restricted_char(c: char) -> bool {
-    c.is_ascii_alphanumeric()
-        || matches!(
-            c,
-            '!' | '#' | '$' | '&' | '-' | '^' | '_' | '.' | '+' | '%' | '*' | '\''
-        )
+    c.is_ascii() && RESTRICTED_CHARS[c as usize]
+}
+
+pub const fn is_restricted_byte(b: u8) -> bool {
+    RESTRICTED_CHARS[b as usize]
+}
+
+/// Returns the length of the leading restricted characters.
+///
+/// Scanning bytes is enough, since the restricted characters are ASCII
+/// and the first byte of a multi-byte character is never ASCII.
+pub fn restricted_len(s: &str) -> usize {
+    s.bytes()
+        .position(|b| !RESTRICTED_CHARS[b as usize])
+        .unwrap_or(s.len())
+}
+
+const RESTRICTED_CHARS: [bool; 256] = {
+    let mut table = [false; 256];
+    let mut i = 0;
+    while i < table.len() {
+        let b = i as u8;
+        table[i] = b.is_ascii_alphanumeric()
+            || matches!(
+                b,
+                b'!' | b'#' | b'$' | b'&' | b'-' | b'^' | b'_' | b'.' | b'+' | b'%' | b'*' | b'\''
+            );
+        i += 1;
+    }
+    table
+};
+
+pub const fn bytes_eq(a: &[u8], b: &[u8], ignore_case: bool) -> bool {
+    if a.len() != b.len() {
+        return false;
+    }
+    let mut i = 0;
+    while i < a.len() {
+        let eq = if ignore_case {
+            a[i].eq_ignore_ascii_case(&b[i])
+        } else {
+            a[i] == b[i]
+        };
+        if !eq {
+            return false;
+        }
+        i += 1;
+    }
+    true
 }
 
 pub const fn is_ows(c: char) -> bool {
     c == ' ' || c == '\t'
 }
 
-fn parse_params(s: &str) -> Result<(Vec<[usize; 4]>, usize), MediaTypeError> {
-    let mut vec = Vec::new();
+/// The UTF-8 byte order mark, which some clients prepend to the header value.
+pub const BOM: &[u8] = "\u{feff}".as_bytes();
+
+/// Removes a leading BOM and the leading OWS, returning the rest and the number of removed bytes.
+pub fn trim_start_ows(s: &str) -> (&str, usize) {
+    let trimmed = s
+        .strip_prefix('\u{feff}')
+        .unwrap_or(s)
+        .trim_start_matches(is_ows);
+    (trimmed, s.len() - trimmed.len())
+}
+
+/// Passes the ranges of the parameters, shifted by `base`, to `push`.
+fn parse_params(
+    s: &str,
+    options: &ParseOptions,
+    base: usize,
+    mut push: impl FnMut([usize; 4]),
+) -> Result<usize, MediaTypeError> {
+    let mut count = 0;
     let mut offset = 0;
     let mut len = 0;
 
-    while let Some((name, value)) = parse_param(&s[offset..])? {
-        vec.push([
-            offset + name.start,
-            offset + name.end,
-            offset + value.start,
-            offset + value.end,
+    while let Some((name, value)) =
+        parse_param(&s[offset..], options.relaxed).map_err(|err| err.offset(offset))?
+    {
+        if count >= options.max_params {
+            return Err(MediaTypeError::TooLarge {
+                position: offset + name.start,
+            });
+        }
+        if value.len() > options.max_param_value_len {
+            return Err(MediaTypeError::TooLarge {
+                position: offset + value.start + options.max_param_value_len,
+            });
+        }
+        push([
+            base + offset + name.start,
+            base + offset + name.end,
+            base + offset + value.start,
+            base + offset + value.end,
         ]);
+        count += 1;
         len = offset + value.end;
         offset += value.end;
     }
 
-    Ok((vec, len))
+    Ok(len)
 }
 
 type ParamRange = (Range<usize>, Range<usize>);
 
-fn parse_param(s: &str) -> Result<Option<ParamRange>, MediaTypeError> {
-    let (ows, right) = match s.split_once(';') {
-        Some((ows, right)) if ows.chars().all(is_ows) && right.chars().all(is_ows) => {
-            return Ok(None)
-        }
-        Some((ows, right)) if ows.chars().all(is_ows) => (ows, right),
-        _ if s.chars().all(is_ows) => return Ok(None),
-        _ => return Err(MediaTypeError::InvalidParams),
+fn parse_param(s: &str, relaxed: bool) -> Result<Option<ParamRange>, MediaTypeError> {
+    let ows = s.len() - s.trim_start_matches(is_ows).len();
+    let right = match s[ows..].strip_prefix(';') {
+        Some(right) if relaxed => right.trim_start_matches(|c| is_ows(c) || c == ';'),
+        Some(right) => right,
+        None if ows == s.len() => return Ok(None),
+        None if s[ows..].starts_with('(') => return Err(MediaTypeError::Comment { position: ows }),
+        None => return Err(MediaTypeError::TrailingData { position: ows }),
     };
+    if right.trim_start_matches(is_ows).is_empty() {
+        return Ok(None);
+    }
+    let right_start = s.len() - right.len();
+
+    let key = right.trim_start_matches(is_ows);
+    if key.starts_with('(') {
+        return Err(MediaTypeError::Comment {
+            position: s.len() - key.len(),
+        });
+    }
+
+    if relaxed {
+        let key_len = restricted_len(right);
+        let rest = right[key_len..].trim_start_matches(is_ows);
+        if key_len > 0 && (rest.is_empty() || rest.starts_with(';')) {
+            let key_range = right_start..right_start + key_len;
+            if let Some(position) = invalid_name_position(&s[key_range.clone()]) {
+                return Err(MediaTypeError::InvalidParamName {
+                    position: right_start + position,
+                });
+            }
+            return Ok(Some((key_range.clone(), key_range.end..key_range.end)));
+        }
+    }
 
     let (name, value) = match right.split_once('=') {
         Some(pair) => pair,
-        _ => return Err(MediaTypeError::InvalidParams),
+        _ => {
+            let key = right.trim_start_matches(is_ows);
+            let position = right_start + right.len() - key.len() + restricted_len(key);
+            return Err(MediaTypeError::InvalidParams { position });
+        }
+    };
+    let value_start = right_start + name.len() + 1;
+    let (name, value, value_start) = if relaxed {
+        let trimmed = value.trim_start_matches(is_ows);
+        (
+            name.trim_end_matches(is_ows),
+            trimmed,
+            value_start + value.len() - trimmed.len(),
+        )
+    } else {
+        (name, value, value_start)
     };
 
     let key_trimmed = name.trim_start_matches(is_ows).len();
-    let key_start = ows.len() + 1 + name.len() - key_trimmed;
+    let key_start = right_start + name.len() - key_trimmed;
     let key_range = key_start..key_start + key_trimmed;
-    if !is_restricted_name(&s[key_range.clone()]) {
-        return Err(MediaTypeError::InvalidParamName);
+    if key_range.is_empty() {
+        return Err(MediaTypeError::MissingParamName {
+            position: key_start,
+        });
+    }
+    if let Some(position) = invalid_name_position(&s[key_range.clone()]) {
+        return Err(MediaTypeError::InvalidParamName {
+            position: key_start + position,
+        });
     }
 
-    let value_start = key_range.end + 1;
     if let Some(value) = value.strip_prefix('\"') {
-        let value_end = value_start + parse_quoted_value(value)? + 1;
+        let value_end =
+            value_start + parse_quoted_value(value).map_err(|err| err.offset(value_start + 1))? + 1;
         let value_range = value_start..value_end;
         Ok(Some((key_range, value_range)))
     } else {
-        let value_end = value_start
-            + value
-                .chars()
-                .take_while(|&c| is_restricted_char(c))
-                .map(char::len_utf8)
-                .sum::<usize>();
-        let value_range = value_start..value_end;
+        let value_len = restricted_len(value);
+        // An empty value followed by junk, like `charset==UTF-8`, is a malformed
+        // parameter rather than trailing data after a complete one.
+        if value_len == 0 && value.starts_with(|c| !is_ows(c) && c != ';' && c != '(') {
+            return Err(MediaTypeError::InvalidParams {
+                position: value_start,
+            });
+        }
+        let value_range = value_start..value_start + value_len;
         Ok(Some((key_range, value_range)))
     }
 }
 
 pub fn parse_quoted_value(s: &str) -> Result<usize, MediaTypeError> {
-    let mut len = 0;
     let mut escaped = false;
-    for c in s.chars() {
-        len += c.len_utf8();
-        match c {
+    for (position, b) in s.bytes().enumerate() {
+        match b {
+            _ if b.is_ascii_control() && b != b'\t' => {
+                return Err(MediaTypeError::InvalidParamValue { position })
+            }
             _ if escaped => {
+                // A non-ASCII character is escaped as a whole:
+                // its continuation bytes are never `\\` or `"`.
                 escaped = false;
             }
-            '\\' => {
+            b'\\' => {
                 escaped = true;
             }
-            '"' => return Ok(len),
-            '\n' => return Err(MediaTypeError::InvalidParamValue),
+            b'"' => return Ok(position + 1),
             _ => (),
         }
     }
-    Err(MediaTypeError::InvalidParamValue)
+    Err(MediaTypeError::InvalidParamValue { position: s.len() })
+}
+
+pub fn parse_quality(s: &str) -> Option<f32> {
+    let (int, frac) = s.split_once('.').unwrap_or((s, ""));
+    if int.is_empty()
+        || frac.len() > 3
+        || !int.chars().chain(frac.chars()).all(|c| c.is_ascii_digit())
+    {
+        return None;
+    }
+    s.parse::<f32>().ok().map(|q| q.clamp(0.0, 1.0))
 }
 
 #[cfg(test)]
@@ -260,15 +481,33 @@ mod tests {
             parse_to_string("image/svg+xml; charset=\"UT\\\"F-8\""),
             Ok("image/svg+xml; charset=\"UT\\\"F-8\"".into())
         );
+        assert_eq!(
+            parse_to_string("text/plain; title=\"a; b=c\"; charset=UTF-8"),
+            Ok("text/plain; title=\"a; b=c\"; charset=UTF-8".into())
+        );
+        assert_eq!(
+            parse_to_string("text/plain; title=\"\"; charset=UTF-8"),
+            Ok("text/plain; title=\"\"; charset=UTF-8".into())
+        );
+        assert_eq!(
+            parse_to_string("text/plain; title=\"\\\\\""),
+            Ok("text/plain; title=\"\\\\\"".into())
+        );
         assert_eq!(
             parse_to_string("multipart/form-data ; boundary=--boundary13234"),
             Ok("multipart/form-data; boundary=--boundary13234".into())
         );
 
         let s = "text/plain";
-        let long_str = format!("{};{}", s, " ".repeat(std::u16::MAX as usize - 2 - s.len()));
+        let long_str = format!("{};{}", s, " ".repeat(u16::MAX as usize - 2 - s.len()));
         assert_eq!(parse_to_string(&long_str), Ok("text/plain".into()));
 
+        assert_eq!(parse_to_string("a/b+c"), Ok("a/b+c".into()));
+        assert_eq!(
+            parse_to_string("application/vnd.foo+xml+zip; charset=UTF-8"),
+            Ok("application/vnd.foo+xml+zip; charset=UTF-8".into())
+        );
+
         let long_name = "a".repeat(Name::MAX_LENGTH);
         let long_str = format!("{}/{}+{}", long_name, long_name, long_name);
         assert_eq!(parse_to_string(&long_str), Ok(long_str));
@@ -276,57 +515,180 @@ mod tests {
 
     #[test]
     fn parse_error() {
-        assert_eq!(parse_to_string(""), Err(MediaTypeError::InvalidTypeName));
+        for (input, err) in [
+            ("", MediaTypeError::EmptyType { position: 0 }),
+            ("text", MediaTypeError::MissingSlash { position: 4 }),
+            ("/plain", MediaTypeError::EmptyType { position: 0 }),
+            ("text/", MediaTypeError::MissingSubtype { position: 5 }),
+            ("text/; a=b", MediaTypeError::MissingSubtype { position: 5 }),
+            ("image/+xml", MediaTypeError::MissingSubtype { position: 6 }),
+            ("image/svg+", MediaTypeError::EmptySuffix { position: 10 }),
+            (
+                "image/svg+; a=b",
+                MediaTypeError::EmptySuffix { position: 10 },
+            ),
+            (
+                "text/plain; =utf-8",
+                MediaTypeError::MissingParamName { position: 12 },
+            ),
+            (
+                "text/plain; a=b;=c",
+                MediaTypeError::MissingParamName { position: 16 },
+            ),
+        ] {
+            assert_eq!(parse_to_string(input), Err(err), "{input:?}");
+        }
+        assert_eq!(
+            parse_to_string("text/plain garbage"),
+            Err(MediaTypeError::TrailingData { position: 11 })
+        );
+        assert_eq!(
+            parse_to_string("text/plain; charset=utf-8 (the charset)"),
+            Err(MediaTypeError::Comment { position: 26 })
+        );
+        assert_eq!(
+            parse_to_string("text/plain(comment)"),
+            Err(MediaTypeError::Comment { position: 10 })
+        );
+        assert_eq!(
+            parse_to_string("text/plain; (comment) charset=utf-8"),
+            Err(MediaTypeError::Comment { position: 12 })
+        );
+        assert_eq!(
+            parse_to_string("text/plain; charset=(utf-8)"),
+            Err(MediaTypeError::Comment { position: 20 })
+        );
+        assert_eq!(
+            parse_to_string("text/plain; charset=\"(utf-8)\""),
+            Ok("text/plain; charset=\"(utf-8)\"".into())
+        );
+        assert_eq!(
+            parse_to_string("text/plain; a=1 x"),
+            Err(MediaTypeError::TrailingData { position: 16 })
+        );
+        assert_eq!(
+            parse_to_string("text/plain; a=1,"),
+            Err(MediaTypeError::TrailingData { position: 15 })
+        );
+        assert_eq!(
+            parse_to_string("text/plain; a=,"),
+            Err(MediaTypeError::InvalidParams { position: 14 })
+        );
+        assert_eq!(
+            parse_to_string("text/plain; a=1 ;"),
+            Ok("text/plain; a=1".into())
+        );
+        assert_eq!(
+            parse_to_string("text/plain; a=1 ; \t"),
+            Ok("text/plain; a=1".into())
+        );
+        assert_eq!(
+            parse_to_string(""),
+            Err(MediaTypeError::EmptyType { position: 0 })
+        );
         assert_eq!(
             parse_to_string("textplain"),
-            Err(MediaTypeError::InvalidTypeName)
+            Err(MediaTypeError::MissingSlash { position: 9 })
         );
         assert_eq!(
             parse_to_string("text//plain"),
-            Err(MediaTypeError::InvalidSubtypeName)
+            Err(MediaTypeError::MissingSubtype { position: 5 })
         );
         assert_eq!(
             parse_to_string(" text/plain"),
-            Err(MediaTypeError::InvalidTypeName)
+            Err(MediaTypeError::InvalidTypeName { position: 0 })
         );
         assert_eq!(
             parse_to_string("text/plain; charsetUTF-8"),
-            Err(MediaTypeError::InvalidParams)
+            Err(MediaTypeError::InvalidParams { position: 24 })
         );
         assert_eq!(
             parse_to_string("text/plain;;"),
-            Err(MediaTypeError::InvalidParams)
+            Err(MediaTypeError::InvalidParams { position: 11 })
         );
         assert_eq!(
             parse_to_string("text/plain;;;"),
-            Err(MediaTypeError::InvalidParams)
+            Err(MediaTypeError::InvalidParams { position: 11 })
+        );
+        assert_eq!(
+            parse_to_string("image/svg+"),
+            Err(MediaTypeError::EmptySuffix { position: 10 })
+        );
+        assert_eq!(
+            parse_to_string("image/svg+xml+"),
+            Err(MediaTypeError::EmptySuffix { position: 14 })
+        );
+        assert_eq!(
+            parse_to_string("image/svg+-xml"),
+            Err(MediaTypeError::InvalidSuffix { position: 10 })
+        );
+        assert_eq!(
+            parse_to_string("image/svg+; charset=UTF-8"),
+            Err(MediaTypeError::EmptySuffix { position: 10 })
         );
         assert_eq!(
             parse_to_string("text/plain; charset=\"UTF-8"),
-            Err(MediaTypeError::InvalidParamValue)
+            Err(MediaTypeError::InvalidParamValue { position: 26 })
+        );
+        assert_eq!(
+            parse_to_string("text/plain; charset=\"UTF-8\\\""),
+            Err(MediaTypeError::InvalidParamValue { position: 28 })
+        );
+        assert_eq!(
+            parse_to_string("text/plain; charset=\"UTF\r-8\""),
+            Err(MediaTypeError::InvalidParamValue { position: 24 })
+        );
+        assert_eq!(
+            parse_to_string("text/plain; charset=\"UTF\\\0-8\""),
+            Err(MediaTypeError::InvalidParamValue { position: 25 })
+        );
+        assert_eq!(
+            parse_to_string("text/plain; charset=\"UTF-8\"x"),
+            Err(MediaTypeError::TrailingData { position: 27 })
         );
         assert_eq!(
             parse_to_string("text/plain; charset==UTF-8"),
-            Err(MediaTypeError::InvalidParams)
+            Err(MediaTypeError::InvalidParams { position: 20 })
         );
         assert_eq!(
             parse_to_string("text/plain; \r\n charset=UTF-8;"),
-            Err(MediaTypeError::InvalidParamName)
+            Err(MediaTypeError::InvalidParamName { position: 12 })
         );
 
-        let long_str = format!("{}/plain", "t".repeat(std::u16::MAX as usize));
+        let long_str = format!("{}/plain", "t".repeat(u16::MAX as usize));
         assert_eq!(
             parse_to_string(&long_str),
-            Err(MediaTypeError::InvalidTypeName)
+            Err(MediaTypeError::InvalidTypeName { position: 127 })
+        );
+
+        let long_str = format!("{}\u{e9}/plain", "t".repeat(Name::MAX_LENGTH * 3 + 1));
+        assert_eq!(
+            parse_to_string(&long_str),
+            Err(MediaTypeError::InvalidTypeName { position: 127 })
         );
 
         assert_eq!(
             parse_to_string("текст/plain"),
-            Err(MediaTypeError::InvalidTypeName)
+            Err(MediaTypeError::InvalidTypeName { position: 0 })
         );
         assert_eq!(
             parse_to_string("text/plain; кодування=UTF-8"),
-            Err(MediaTypeError::InvalidParamName)
+            Err(MediaTypeError::InvalidParamName { position: 12 })
         );
     }
+
+    #[test]
+    fn quality() {
+        assert_eq!(parse_quality("1"), Some(1.0));
+        assert_eq!(parse_quality("0"), Some(0.0));
+        assert_eq!(parse_quality("0.5"), Some(0.5));
+        assert_eq!(parse_quality("0.125"), Some(0.125));
+        assert_eq!(parse_quality("1."), Some(1.0));
+        assert_eq!(parse_quality("1.5"), Some(1.0));
+        assert_eq!(parse_quality("0.1234"), None);
+        assert_eq!(parse_quality(".5"), None);
+        assert_eq!(parse_quality("-0.5"), None);
+        assert_eq!(parse_quality("0.5a"), None);
+        assert_eq!(parse_quality(""), None);
+    }
 }
@@ -0,0 +1,84 @@
+/// Limits applied by [`MediaType::parse_with`](crate::MediaType::parse_with).
+///
+/// The default limits are generous enough for real-world headers while bounding
+/// the work and the allocation for adversarial input.
+///
+/// ```
+/// # use mediatype::{MediaType, MediaTypeError, ParseOptions};
+/// let options = ParseOptions::new().with_max_params(1);
+/// assert!(MediaType::parse_with("text/plain; charset=UTF-8", &options).is_ok());
+/// assert_eq!(
+///     MediaType::parse_with("text/plain; charset=UTF-8; format=fixed", &options),
+///     Err(MediaTypeError::TooLarge { position: 27 })
+/// );
+/// ```
+#[non_exhaustive]
+#[derive(Debug, Copy, Clone, Eq, PartialEq, Hash)]
+pub struct ParseOptions {
+    /// The maximum byte length of the input. The default is `4096`.
+    pub max_length: usize,
+
+    /// The maximum number of parameters. The default is `64`.
+    pub max_params: usize,
+
+    /// The maximum byte length of a parameter value, including quotes. The default is `1024`.
+    pub max_param_value_len: usize,
+
+    /// Tolerates the deviations accepted by
+    /// [`MediaType::parse_relaxed`](crate::MediaType::parse_relaxed). The default is `false`.
+    pub relaxed: bool,
+}
+
+impl ParseOptions {
+    pub(crate) const UNLIMITED: Self = Self {
+        max_length: usize::MAX,
+        max_params: usize::MAX,
+        max_param_value_len: usize::MAX,
+        relaxed: false,
+    };
+
+    /// Constructs a `ParseOptions` with the default limits.
+    #[must_use]
+    pub const fn new() -> Self {
+        Self {
+            max_length: 4096,
+            max_params: 64,
+            max_param_value_len: 1024,
+            relaxed: false,
+        }
+    }
+
+    /// Sets [`max_length`](Self::max_length).
+    #[must_use]
+    pub const fn with_max_length(mut self, max_length: usize) -> Self {
+        self.max_length = max_length;
+        self
+    }
+
+    /// Sets [`max_params`](Self::max_params).
+    #[must_use]
+    pub const fn with_max_params(mut self, max_params: usize) -> Self {
+        self.max_params = max_params;
+        self
+    }
+
+    /// Sets [`max_param_value_len`](Self::max_param_value_len).
+    #[must_use]
+    pub const fn with_max_param_value_len(mut self, max_param_value_len: usize) -> Self {
+        self.max_param_value_len = max_param_value_len;
+        self
+    }
+
+    /// Sets [`relaxed`](Self::relaxed).
+    #[must_use]
+    pub const fn with_relaxed(mut self, relaxed: bool) -> Self {
+        self.relaxed = relaxed;
+        self
+    }
+}
+
+impl Default for ParseOptions {
+    fn default() -> Self {
+        Self::new()
+    }
+}
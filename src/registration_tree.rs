@@ -0,0 +1,61 @@
+use super::name::*;
+
+/// A registration tree of a media subtype.
+///
+/// The tree is determined by the prefix of the subtype, ignoring ASCII case.
+///
+/// ```
+/// # use mediatype::{MediaType, RegistrationTree};
+/// let excel = MediaType::parse("application/vnd.ms-excel").unwrap();
+/// assert_eq!(excel.registration_tree(), RegistrationTree::Vendor);
+///
+/// let tar = MediaType::parse("application/x-tar").unwrap();
+/// assert_eq!(tar.registration_tree(), RegistrationTree::Unregistered);
+/// ```
+#[derive(Debug, Copy, Clone, Eq, PartialEq, Hash)]
+pub enum RegistrationTree {
+    /// The standards tree, which has no prefix.
+    Standard,
+    /// The vendor tree, prefixed with `vnd.`.
+    Vendor,
+    /// The personal or vanity tree, prefixed with `prs.`.
+    Personal,
+    /// Unregistered subtypes, prefixed with `x.` or `x-`.
+    Unregistered,
+}
+
+impl RegistrationTree {
+    /// Returns the registration tree of a subtype.
+    #[must_use]
+    pub fn of(subty: Name) -> Self {
+        if subty.starts_with_ignore_case("vnd.") {
+            Self::Vendor
+        } else if subty.starts_with_ignore_case("prs.") {
+            Self::Personal
+        } else if subty.starts_with_ignore_case("x.") || subty.starts_with_ignore_case("x-") {
+            Self::Unregistered
+        } else {
+            Self::Standard
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::MediaType;
+
+    #[test]
+    fn of() {
+        let tree = |s| MediaType::parse(s).unwrap().registration_tree();
+        assert_eq!(tree("application/vnd.ms-excel"), RegistrationTree::Vendor);
+        assert_eq!(tree("application/VND.MS-EXCEL"), RegistrationTree::Vendor);
+        assert_eq!(tree("application/prs.foo"), RegistrationTree::Personal);
+        assert_eq!(tree("application/x-tar"), RegistrationTree::Unregistered);
+        assert_eq!(tree("application/X.foo"), RegistrationTree::Unregistered);
+        assert_eq!(tree("application/json"), RegistrationTree::Standard);
+        assert_eq!(tree("application/vnd"), RegistrationTree::Standard);
+        assert_eq!(tree("application/xml"), RegistrationTree::Standard);
+        assert_eq!(tree("image/svg+xml"), RegistrationTree::Standard);
+    }
+}
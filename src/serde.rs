@@ -1,18 +1,20 @@
 #![cfg(feature = "serde")]
 
 use super::{media_type::*, media_type_buf::*};
+use alloc::borrow::Cow;
 use serde::{Deserialize, Deserializer, Serialize, Serializer};
-use std::borrow::Cow;
 
+#[cfg_attr(docsrs, doc(cfg(feature = "serde")))]
 impl<'a> Serialize for MediaType<'a> {
     fn serialize<S>(&self, serializer: S) -> Result<S::Ok, S::Error>
     where
         S: Serializer,
     {
-        self.to_string().serialize(serializer)
+        serializer.collect_str(self)
     }
 }
 
+#[cfg_attr(docsrs, doc(cfg(feature = "serde")))]
 impl<'de> Deserialize<'de> for MediaType<'de> {
     fn deserialize<D>(deserializer: D) -> Result<Self, D::Error>
     where
@@ -24,15 +26,17 @@ impl<'de> Deserialize<'de> for MediaType<'de> {
     }
 }
 
+#[cfg_attr(docsrs, doc(cfg(feature = "serde")))]
 impl Serialize for MediaTypeBuf {
     fn serialize<S>(&self, serializer: S) -> Result<S::Ok, S::Error>
     where
         S: Serializer,
     {
-        self.to_string().serialize(serializer)
+        serializer.collect_str(self)
     }
 }
 
+#[cfg_attr(docsrs, doc(cfg(feature = "serde")))]
 impl<'de> Deserialize<'de> for MediaTypeBuf {
     fn deserialize<D>(deserializer: D) -> Result<Self, D::Error>
     where
@@ -65,4 +69,13 @@ mod tests {
         let decoded: MediaTypeBuf = serde_json::from_value(value).unwrap();
         assert_eq!(original, decoded);
     }
+
+    #[test]
+    fn serde_error() {
+        let err = serde_json::from_str::<MediaTypeBuf>("\"text/\"").unwrap_err();
+        assert_eq!(err.to_string(), "missing subtype at offset 5");
+        let err = serde_json::from_str::<MediaType>("\"text\"").unwrap_err();
+        assert_eq!(err.to_string(), "missing `/` after the type at offset 4");
+        assert!(serde_json::from_str::<MediaTypeBuf>("42").is_err());
+    }
 }
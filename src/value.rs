@@ -1,10 +1,12 @@
-use super::parse::*;
-use std::{
-    borrow::Cow,
+use super::{error::*, parse::*};
+use alloc::{borrow::Cow, string::String, vec};
+use core::{
     cmp::Ordering,
     fmt,
     hash::{Hash, Hasher},
     iter,
+    ops::Deref,
+    str::FromStr,
 };
 
 /// A media-type parameter value.
@@ -26,20 +28,41 @@ use std::{
 /// assert_eq!(double_quoted.unquoted_str(), " \" ");
 /// ```
 #[derive(Debug, Copy, Clone)]
-pub struct Value<'a>(&'a str);
+pub struct Value<'a>(&'a str, bool);
 
 impl<'a> Value<'a> {
+    /// The empty value, like the value of `text/plain; flag=`.
+    pub const EMPTY: Value<'static> = Value("", false);
+
+    /// The value of a parameter without `=`, like `text/plain; flag`,
+    /// which only [`MediaType::parse_relaxed`](crate::MediaType::parse_relaxed) accepts.
+    ///
+    /// It is empty like [`EMPTY`](Self::EMPTY), but is not equal to it,
+    /// and the parameter is written without `=`.
+    ///
+    /// ```
+    /// # use mediatype::{MediaType, Name, ReadParams, Value};
+    /// assert!(MediaType::parse("text/plain; flag").is_err());
+    /// let media_type = MediaType::parse_relaxed("text/plain; flag").unwrap();
+    /// let flag = media_type.get_param(Name::new("flag").unwrap()).unwrap();
+    /// assert_eq!(flag, Value::ABSENT);
+    /// assert!(flag.is_absent());
+    /// assert_ne!(flag, Value::EMPTY);
+    /// assert_eq!(media_type.to_string(), "text/plain; flag");
+    /// ```
+    pub const ABSENT: Value<'static> = Value("", true);
+
     /// Constructs a `Value`.
     ///
     /// If the string is not valid as a value, returns `None`.
     #[must_use]
     pub fn new(s: &'a str) -> Option<Self> {
         if let Some(quoted) = s.strip_prefix('\"') {
-            if quoted.len() > 1 && parse_quoted_value(quoted) == Ok(quoted.len()) {
-                return Some(Self(s));
+            if !quoted.is_empty() && parse_quoted_value(quoted) == Ok(quoted.len()) {
+                return Some(Self(s, false));
             }
         } else if is_restricted_str(s) {
-            return Some(Self(s));
+            return Some(Self(s, false));
         }
         None
     }
@@ -50,10 +73,61 @@ impl<'a> Value<'a> {
         self.0
     }
 
+    /// Returns the byte length of the underlying string, including quotes.
+    #[must_use]
+    pub const fn len(&self) -> usize {
+        self.0.len()
+    }
+
+    /// Returns `true` if the underlying string is empty.
+    ///
+    /// An empty quoted string `""` is not empty.
+    #[must_use]
+    pub const fn is_empty(&self) -> bool {
+        self.0.is_empty()
+    }
+
+    /// Returns `true` if the value is [`ABSENT`](Self::ABSENT),
+    /// which means the parameter has no `=`.
+    #[must_use]
+    pub const fn is_absent(&self) -> bool {
+        self.1
+    }
+
+    /// Returns `true` if the value is a quoted string.
+    ///
+    /// ```
+    /// # use mediatype::Value;
+    /// assert!(Value::new("\"UTF-8\"").unwrap().is_quoted());
+    /// assert!(!Value::new("UTF-8").unwrap().is_quoted());
+    /// ```
+    #[must_use]
+    pub const fn is_quoted(&self) -> bool {
+        !self.0.is_empty() && self.0.as_bytes()[0] == b'"'
+    }
+
     /// Returns the unquoted string.
+    ///
+    /// This is kept for compatibility and delegates to [`unquoted`](Self::unquoted),
+    /// which also borrows for the lifetime of the underlying string.
     #[must_use]
     pub fn unquoted_str(&self) -> Cow<'_, str> {
-        if self.0.starts_with('"') {
+        self.unquoted()
+    }
+
+    /// Returns the content with the surrounding quotes and escapes removed.
+    ///
+    /// The result borrows from the underlying string unless the value contains escapes.
+    ///
+    /// ```
+    /// # use mediatype::Value;
+    /// # use std::borrow::Cow;
+    /// assert_eq!(Value::new("\"a \\\"b\\\"\"").unwrap().unquoted(), "a \"b\"");
+    /// assert!(matches!(Value::new("\"a b\"").unwrap().unquoted(), Cow::Borrowed("a b")));
+    /// ```
+    #[must_use]
+    pub fn unquoted(&self) -> Cow<'a, str> {
+        if self.is_quoted() {
             let inner = &self.0[1..self.0.len() - 1];
             if inner.contains('\\') {
                 let mut s = String::with_capacity(inner.len());
@@ -81,8 +155,31 @@ impl<'a> Value<'a> {
         }
     }
 
+    /// Parses the unquoted content with [`FromStr`].
+    ///
+    /// Unlike [`str::parse`] through `Deref`, a quoted string is unquoted first.
+    ///
+    /// ```
+    /// # use mediatype::Value;
+    /// assert_eq!(Value::new("3").unwrap().parse_unquoted::<u32>(), Ok(3));
+    /// assert_eq!(Value::new("\"0.9\"").unwrap().parse_unquoted::<f32>(), Ok(0.9));
+    /// assert!(Value::new("\"0.9\"").unwrap().parse::<f32>().is_err());
+    /// assert!(Value::new("UTF-8").unwrap().parse_unquoted::<u32>().is_err());
+    /// ```
+    ///
+    /// # Errors
+    ///
+    /// Returns the error of [`FromStr::from_str`] if the content fails to be parsed.
+    pub fn parse_unquoted<T: FromStr>(&self) -> Result<T, T::Err> {
+        self.unquoted().parse()
+    }
+
     /// Generates a quoted string if necessary.
     ///
+    /// Control characters other than tab can not be represented even in a quoted string,
+    /// and the result is not a valid value if `s` contains them.
+    /// Use [`try_quote`](Self::try_quote) to detect it.
+    ///
     /// ```
     /// # use mediatype::Value;
     /// assert_eq!(Value::quote("UTF-8"), "UTF-8");
@@ -109,8 +206,46 @@ impl<'a> Value<'a> {
         }
     }
 
+    /// Generates a quoted string if necessary, rejecting characters that can not be quoted.
+    ///
+    /// The result can be passed to [`Value::new`].
+    ///
+    /// ```
+    /// # use mediatype::{MediaTypeError, Value};
+    /// let quoted = Value::try_quote("my report.pdf").unwrap();
+    /// let filename = Value::new(&quoted).unwrap();
+    /// assert_eq!(filename.to_string(), "\"my report.pdf\"");
+    /// assert_eq!(filename.unquoted(), "my report.pdf");
+    ///
+    /// assert_eq!(
+    ///     Value::try_quote("a\nb"),
+    ///     Err(MediaTypeError::InvalidParamValue { position: 1 })
+    /// );
+    /// ```
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if `s` contains control characters other than tab.
+    pub fn try_quote(s: &str) -> Result<Cow<'_, str>, MediaTypeError> {
+        match s.find(|c: char| c.is_ascii_control() && c != '\t') {
+            Some(position) => Err(MediaTypeError::InvalidParamValue { position }),
+            None => Ok(Self::quote(s)),
+        }
+    }
+
     pub(crate) const fn new_unchecked(s: &'a str) -> Self {
-        Self(s)
+        Self(s, false)
+    }
+
+    /// Constructs the value of `param`, the ranges of a parameter in `s`, without validation.
+    ///
+    /// The value range of a parameter without `=` starts at the end of the name.
+    pub(crate) fn from_param(s: &'a str, param: &[usize; 4]) -> Self {
+        if param[1] == param[2] {
+            Self::ABSENT
+        } else {
+            Self::new_unchecked(&s[param[2]..param[3]])
+        }
     }
 }
 
@@ -120,9 +255,38 @@ impl<'a> fmt::Display for Value<'a> {
     }
 }
 
+/// Constructs a `Value` in the same way as [`Value::new`].
+///
+/// ```
+/// # use mediatype::{MediaTypeError, Value};
+/// let utf8: Value = "UTF-8".try_into().unwrap();
+/// assert_eq!(utf8, Value::new("UTF-8").unwrap());
+/// assert_eq!(
+///     Value::try_from("UTF 8"),
+///     Err(MediaTypeError::InvalidParamValue { position: 3 })
+/// );
+/// ```
+impl<'a> TryFrom<&'a str> for Value<'a> {
+    type Error = MediaTypeError;
+
+    fn try_from(s: &'a str) -> Result<Self, Self::Error> {
+        Self::new(s).ok_or_else(|| MediaTypeError::InvalidParamValue {
+            position: invalid_value_position(s).unwrap_or(0),
+        })
+    }
+}
+
+impl<'a> Deref for Value<'a> {
+    type Target = str;
+
+    fn deref(&self) -> &str {
+        self.0
+    }
+}
+
 impl<'a> PartialEq for Value<'a> {
     fn eq(&self, other: &Self) -> bool {
-        self.unquoted_str() == other.unquoted_str()
+        self.is_absent() == other.is_absent() && self.unquoted_str() == other.unquoted_str()
     }
 }
 
@@ -136,13 +300,16 @@ impl<'a> PartialOrd for Value<'a> {
 
 impl<'a> Ord for Value<'a> {
     fn cmp(&self, other: &Self) -> Ordering {
-        self.unquoted_str().cmp(&other.unquoted_str())
+        self.unquoted_str()
+            .cmp(&other.unquoted_str())
+            .then(self.is_absent().cmp(&other.is_absent()))
     }
 }
 
 impl<'a> Hash for Value<'a> {
     fn hash<H: Hasher>(&self, state: &mut H) {
         self.unquoted_str().hash(state);
+        self.is_absent().hash(state);
     }
 }
 
@@ -222,11 +389,107 @@ impl<'a> PartialOrd<&Cow<'_, str>> for Value<'a> {
 mod tests {
     use super::*;
 
+    #[test]
+    fn new() {
+        assert!(Value::new("UTF-8").is_some());
+        assert!(Value::new("\"\"").is_some());
+        assert!(Value::new("\"a; b\"").is_some());
+        assert!(Value::new("\"a\tb\"").is_some());
+        assert!(Value::new("\"").is_none());
+        assert!(Value::new("\"a\"b\"").is_none());
+        assert!(Value::new("\"a\\\"").is_none());
+        assert!(Value::new("\"a\rb\"").is_none());
+        assert!(Value::new("a b").is_none());
+    }
+
+    #[test]
+    fn len() {
+        assert_eq!(Value::new("UTF-8").unwrap().len(), 5);
+        assert_eq!(Value::new("\"UTF-8\"").unwrap().len(), 7);
+        assert!(Value::new("").unwrap().is_empty());
+        assert!(!Value::new("\"\"").unwrap().is_empty());
+        assert!(Value::new("\"UTF-8\"").unwrap().starts_with('"'));
+    }
+
+    #[test]
+    fn try_from() {
+        assert_eq!(
+            Value::try_from("\"a b\"").map(|v| v.as_str()),
+            Ok("\"a b\"")
+        );
+        assert_eq!(Value::try_from("").map(|v| v.as_str()), Ok(""));
+        assert_eq!(
+            Value::try_from("\""),
+            Err(MediaTypeError::InvalidParamValue { position: 1 })
+        );
+        assert_eq!(
+            Value::try_from("\"abc\"d"),
+            Err(MediaTypeError::InvalidParamValue { position: 5 })
+        );
+    }
+
+    #[test]
+    fn try_quote() {
+        for s in ["UTF-8", "", "hello world", "a\tb", "\"a\\b\"", "é", "a;b=c"] {
+            let quoted = Value::try_quote(s).unwrap();
+            let value = Value::new(&quoted).unwrap();
+            assert_eq!(value.unquoted(), s);
+        }
+        assert_eq!(Value::try_quote("UTF-8").unwrap(), "UTF-8");
+        assert_eq!(
+            Value::try_quote("\u{7f}"),
+            Err(MediaTypeError::InvalidParamValue { position: 0 })
+        );
+        assert_eq!(
+            Value::try_quote("ab\r\n"),
+            Err(MediaTypeError::InvalidParamValue { position: 2 })
+        );
+    }
+
+    #[test]
+    fn is_quoted() {
+        assert!(Value::new("\"\"").unwrap().is_quoted());
+        assert!(Value::new("\"UTF-8\"").unwrap().is_quoted());
+        assert!(!Value::new("UTF-8").unwrap().is_quoted());
+        assert!(!Value::new("").unwrap().is_quoted());
+    }
+
+    #[test]
+    fn unquoted() {
+        let s = String::from("\"a\\\\b\"");
+        let value = Value::new(&s).unwrap();
+        assert_eq!(value.unquoted(), "a\\b");
+        assert_eq!(Value::new("\"\"").unwrap().unquoted(), "");
+        assert_eq!(Value::new("UTF-8").unwrap().unquoted(), "UTF-8");
+
+        let unquoted: Cow<'static, str> = Value::new("\"a b\"").unwrap().unquoted();
+        assert!(matches!(unquoted, Cow::Borrowed("a b")));
+    }
+
+    #[test]
+    fn parse_unquoted() {
+        assert_eq!(Value::new("1").unwrap().parse_unquoted::<u8>(), Ok(1));
+        assert_eq!(Value::new("\"1\"").unwrap().parse_unquoted::<u8>(), Ok(1));
+        assert_eq!(Value::new("\"\\1\"").unwrap().parse_unquoted::<u8>(), Ok(1));
+        assert_eq!(Value::new("0.5").unwrap().parse_unquoted::<f64>(), Ok(0.5));
+        assert_eq!(
+            Value::new("true").unwrap().parse_unquoted::<bool>(),
+            Ok(true)
+        );
+        assert!(Value::new("\" 1\"")
+            .unwrap()
+            .parse_unquoted::<u8>()
+            .is_err());
+        assert!(Value::new("256").unwrap().parse_unquoted::<u8>().is_err());
+        assert!(Value::EMPTY.parse_unquoted::<u8>().is_err());
+    }
+
     #[test]
     fn unquoted_str() {
         assert_eq!(Value::new("\"\\a\\\\\"").unwrap().unquoted_str(), "a\\");
         assert_eq!(Value::new("\"\\\"\"").unwrap().unquoted_str(), "\"");
         assert_eq!(Value::new("\"\\a\\b\\c\"").unwrap().unquoted_str(), "abc");
+        assert_eq!(Value::new("\"\"").unwrap().unquoted_str(), "");
         assert_eq!(
             Value::new("\" \\\"What's wrong\\?\\\" \"")
                 .unwrap()
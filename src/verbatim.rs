@@ -0,0 +1,141 @@
+use super::{error::*, media_type::*};
+use core::{
+    fmt,
+    hash::{Hash, Hasher},
+    ops::Deref,
+};
+
+/// A [`MediaType`] which keeps the exact input for byte-exact output.
+///
+/// The [`Display`](fmt::Display) output of `MediaType` is normalized, so the spacing
+/// after `;` and the surrounding whitespace may change. `Verbatim` writes the input
+/// as it is, which matters when the raw header is hashed or signed,
+/// or echoed back with the exact letter case.
+/// It is immutable so that the input always matches the parsed media type,
+/// which is accessible through [`Deref`].
+///
+/// The owned [`MediaTypeBuf`](crate::MediaTypeBuf) keeps the input in
+/// [`as_str`](crate::MediaTypeBuf::as_str) as well, except the leading whitespace.
+///
+/// ```
+/// use mediatype::{names::*, MediaType, ReadParams, Verbatim};
+///
+/// let verbatim = Verbatim::parse("text/plain;charset=utf-8").unwrap();
+/// assert_eq!(verbatim.to_string(), "text/plain;charset=utf-8");
+/// assert_eq!(verbatim.get_param(CHARSET).unwrap(), "utf-8");
+///
+/// let media_type = MediaType::parse("text/plain;charset=utf-8").unwrap();
+/// assert_eq!(media_type.to_string(), "text/plain; charset=utf-8");
+/// assert_eq!(*verbatim, media_type);
+/// ```
+#[derive(Debug, Clone)]
+pub struct Verbatim<'a> {
+    media_type: MediaType<'a>,
+    source: &'a str,
+}
+
+impl<'a> Verbatim<'a> {
+    /// Parses `s` like [`MediaType::parse`], keeping `s` as it is.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the string fails to be parsed.
+    pub fn parse(s: &'a str) -> Result<Self, MediaTypeError> {
+        Ok(Self {
+            media_type: MediaType::parse(s)?,
+            source: s,
+        })
+    }
+
+    /// Returns the input string, including the surrounding whitespace.
+    #[must_use]
+    pub const fn as_str(&self) -> &'a str {
+        self.source
+    }
+
+    /// Returns the parsed media type, discarding the input.
+    #[must_use]
+    pub fn into_inner(self) -> MediaType<'a> {
+        self.media_type
+    }
+}
+
+impl<'a> Deref for Verbatim<'a> {
+    type Target = MediaType<'a>;
+
+    fn deref(&self) -> &MediaType<'a> {
+        &self.media_type
+    }
+}
+
+impl<'a> AsRef<str> for Verbatim<'a> {
+    fn as_ref(&self) -> &str {
+        self.source
+    }
+}
+
+impl<'a> fmt::Display for Verbatim<'a> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(self.source)
+    }
+}
+
+impl<'a, 'b> PartialEq<Verbatim<'b>> for Verbatim<'a> {
+    fn eq(&self, other: &Verbatim<'b>) -> bool {
+        self.media_type == other.media_type
+    }
+}
+
+impl<'a> Eq for Verbatim<'a> {}
+
+impl<'a> Hash for Verbatim<'a> {
+    fn hash<H: Hasher>(&self, state: &mut H) {
+        self.media_type.hash(state);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{names::*, values::*, ReadParams};
+    use alloc::string::ToString;
+
+    #[test]
+    fn parse() {
+        for s in [
+            "text/plain",
+            "text/plain;charset=UTF-8",
+            " Text/Plain ;  charset=\"UTF-8\" ; ",
+            "image/svg+xml;charset=UTF-8;format=flowed",
+            "\u{feff}TEXT/Plain",
+            "text/plain; a=\"b ; c\" ",
+        ] {
+            let verbatim = Verbatim::parse(s).unwrap();
+            assert_eq!(verbatim.to_string(), s);
+            assert_eq!(verbatim.as_str(), s);
+            assert_eq!(verbatim.clone().into_inner(), MediaType::parse(s).unwrap());
+        }
+
+        let verbatim = Verbatim::parse("image/svg+xml;charset=UTF-8").unwrap();
+        assert_eq!(verbatim.ty, IMAGE);
+        assert_eq!(verbatim.suffix, Some(XML));
+        assert_eq!(verbatim.get_param(CHARSET), Some(UTF_8));
+
+        assert_eq!(
+            Verbatim::parse("text/plain;charset"),
+            Err(MediaTypeError::InvalidParams { position: 18 })
+        );
+    }
+
+    #[test]
+    fn eq() {
+        assert_eq!(
+            Verbatim::parse("text/plain;charset=UTF-8").unwrap(),
+            Verbatim::parse("TEXT/PLAIN; charset=\"UTF-8\"").unwrap()
+        );
+        assert_ne!(
+            Verbatim::parse("text/plain").unwrap(),
+            Verbatim::parse("text/html").unwrap()
+        );
+    }
+}
@@ -0,0 +1,220 @@
+use super::{error::*, media_type_buf::*, name::*, parse::*, value::*};
+use alloc::{borrow::Cow, string::String, vec::Vec};
+
+impl MediaTypeBuf {
+    /// Parses a media type with the [WHATWG MIME Sniffing] algorithm.
+    ///
+    /// The algorithm is used by browsers, and differs from [`MediaType::parse`](crate::MediaType::parse):
+    ///
+    /// - Leading and trailing whitespace, including CR and LF, is removed.
+    /// - The type, the subtype and the parameter names are converted to lowercase.
+    /// - Invalid parameters are skipped instead of failing.
+    /// - If the same parameter name appears more than once, the **first** one is kept.
+    ///   This is the opposite of [`ReadParams::get_param`](crate::ReadParams::get_param),
+    ///   which returns the last one for a media type parsed by the other functions.
+    /// - Unterminated quoted strings are accepted, and the text after the closing quote
+    ///   is ignored.
+    ///
+    /// Names that are valid in WHATWG but not in this crate, such as `a|b`, are treated
+    /// as invalid. The type and the subtype fail to be parsed, and the parameter is skipped.
+    ///
+    /// The result is owned since the values are normalized:
+    /// they are unescaped and quoted again only if necessary.
+    ///
+    /// ```
+    /// # use mediatype::{names::*, MediaTypeBuf, ReadParams};
+    /// let media_type =
+    ///     MediaTypeBuf::parse_whatwg(" TEXT/HTML;charset=gbk;charset=windows-1255;x=\"y\"z\r\n").unwrap();
+    /// assert_eq!(media_type.to_string(), "text/html; charset=gbk; x=y");
+    /// assert_eq!(media_type.get_param(CHARSET).unwrap(), "gbk");
+    /// ```
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the type or the subtype is invalid.
+    ///
+    /// [WHATWG MIME Sniffing]: https://mimesniff.spec.whatwg.org/#parsing-a-mime-type
+    pub fn parse_whatwg(s: &str) -> Result<Self, MediaTypeError> {
+        let trimmed = s.trim_start_matches(is_http_whitespace);
+        let start = s.len() - trimmed.len();
+        let s = trimmed.trim_end_matches(is_http_whitespace);
+
+        let (ty, right) = match s.split_once('/') {
+            Some(pair) => pair,
+            None if s.is_empty() => return Err(MediaTypeError::EmptyType { position: start }),
+            None => {
+                return Err(match invalid_name_position(s) {
+                    Some(position) => MediaTypeError::InvalidTypeName {
+                        position: start + position,
+                    },
+                    None => MediaTypeError::MissingSlash {
+                        position: start + s.len(),
+                    },
+                });
+            }
+        };
+        if ty.is_empty() {
+            return Err(MediaTypeError::EmptyType { position: start });
+        }
+        if let Some(position) = invalid_name_position(ty) {
+            return Err(MediaTypeError::InvalidTypeName {
+                position: start + position,
+            });
+        }
+
+        let (subty, mut params) = right.split_at(right.find(';').unwrap_or(right.len()));
+        let subty = subty.trim_end_matches(is_http_whitespace);
+        let subty_start = start + ty.len() + 1;
+        if let Some(position) = subty.find(|c| !is_restricted_char(c)) {
+            return Err(MediaTypeError::InvalidSubtypeName {
+                position: subty_start + position,
+            });
+        }
+
+        let mut out = String::with_capacity(s.len());
+        out.extend(ty.chars().map(|c| c.to_ascii_lowercase()));
+        out.push('/');
+        out.extend(subty.chars().map(|c| c.to_ascii_lowercase()));
+
+        let mut names: Vec<&str> = Vec::new();
+        while let Some(right) = params.strip_prefix(';') {
+            let right = right.trim_start_matches(is_http_whitespace);
+            let (name, right) = right.split_at(right.find([';', '=']).unwrap_or(right.len()));
+            let right = match right.strip_prefix('=') {
+                Some(right) => right,
+                None => {
+                    params = right;
+                    continue;
+                }
+            };
+
+            let is_quoted = right.starts_with('"');
+            let value: Cow<str> = if let Some(quoted) = right.strip_prefix('"') {
+                let (value, len) = collect_quoted(quoted);
+                let right = &quoted[len..];
+                params = &right[right.find(';').unwrap_or(right.len())..];
+                value.into()
+            } else {
+                let (value, right) = right.split_at(right.find(';').unwrap_or(right.len()));
+                params = right;
+                value.trim_end_matches(is_http_whitespace).into()
+            };
+
+            if value.is_empty() && !is_quoted {
+                continue;
+            }
+            if Name::new(name).is_none()
+                || !value.chars().all(is_http_quoted_string_token)
+                || names.iter().any(|prev| prev.eq_ignore_ascii_case(name))
+            {
+                continue;
+            }
+            names.push(name);
+            out.push_str("; ");
+            out.extend(name.chars().map(|c| c.to_ascii_lowercase()));
+            out.push('=');
+            if value.is_empty() {
+                out.push_str("\"\"");
+            } else {
+                out.push_str(&Value::quote(&value));
+            }
+        }
+
+        Self::from_string(out).map_err(|err| err.offset(start))
+    }
+}
+
+const fn is_http_whitespace(c: char) -> bool {
+    matches!(c, '\n' | '\r' | '\t' | ' ')
+}
+
+const fn is_http_quoted_string_token(c: char) -> bool {
+    matches!(c, '\t' | ' '..='~' | '\u{80}'..='\u{ff}')
+}
+
+/// Collects a quoted string after the opening quote, returning the unescaped value
+/// and the length including the closing quote.
+fn collect_quoted(s: &str) -> (String, usize) {
+    let mut value = String::new();
+    let mut chars = s.char_indices();
+    while let Some((i, c)) = chars.next() {
+        match c {
+            '"' => return (value, i + 1),
+            '\\' => match chars.next() {
+                Some((_, c)) => value.push(c),
+                None => value.push('\\'),
+            },
+            _ => value.push(c),
+        }
+    }
+    (value, s.len())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{names::*, ReadParams};
+
+    fn parse(s: &str) -> Result<String, MediaTypeError> {
+        MediaTypeBuf::parse_whatwg(s).map(|media_type| media_type.to_string())
+    }
+
+    #[test]
+    fn parse_whatwg() {
+        for (input, expected) in [
+            ("text/html;charset=gbk", "text/html; charset=gbk"),
+            ("TEXT/HTML;CHARSET=GBK", "text/html; charset=GBK"),
+            (
+                "\n\r\t text/html \t;\n charset=gbk ",
+                "text/html; charset=gbk",
+            ),
+            (
+                "text/html;charset=gbk;charset=windows-1255",
+                "text/html; charset=gbk",
+            ),
+            ("text/html;charset=;charset=gbk", "text/html; charset=gbk"),
+            ("text/html;charset;charset=gbk", "text/html; charset=gbk"),
+            ("text/html;charset =gbk", "text/html"),
+            ("text/html;charset= gbk", "text/html; charset=\" gbk\""),
+            ("text/html;charset=\"gbk", "text/html; charset=gbk"),
+            (
+                "text/html;charset=\"gbk\"x;a=b",
+                "text/html; charset=gbk; a=b",
+            ),
+            (
+                "text/html;charset=\"g\\\"bk\\",
+                "text/html; charset=\"g\\\"bk\\\\\"",
+            ),
+            ("text/html;charset=\"\"", "text/html; charset=\"\""),
+            ("text/html;a|b=c;d=e", "text/html; d=e"),
+            ("image/svg+xml;", "image/svg+xml"),
+        ] {
+            assert_eq!(parse(input).as_deref(), Ok(expected), "{input:?}");
+        }
+
+        let media_type = MediaTypeBuf::parse_whatwg("text/html;charset=();CHARSET=gbk").unwrap();
+        assert_eq!(media_type.get_param(CHARSET).unwrap().unquoted(), "()");
+    }
+
+    #[test]
+    fn errors() {
+        for (input, err) in [
+            ("", MediaTypeError::EmptyType { position: 0 }),
+            ("  text", MediaTypeError::MissingSlash { position: 6 }),
+            ("/html", MediaTypeError::EmptyType { position: 0 }),
+            (
+                " text /html",
+                MediaTypeError::InvalidTypeName { position: 5 },
+            ),
+            ("text/", MediaTypeError::MissingSubtype { position: 5 }),
+            ("text/;a=b", MediaTypeError::MissingSubtype { position: 5 }),
+            (
+                "text/h|ml",
+                MediaTypeError::InvalidSubtypeName { position: 6 },
+            ),
+            (" text/svg+", MediaTypeError::EmptySuffix { position: 10 }),
+        ] {
+            assert_eq!(parse(input), Err(err), "{input:?}");
+        }
+    }
+}
@@ -0,0 +1,69 @@
+use mediatype::{MediaType, MediaTypeBuf};
+use std::{
+    alloc::{GlobalAlloc, Layout, System},
+    cell::Cell,
+};
+
+/// Counts the allocations so that zero-copy conversions can be verified.
+///
+/// The counter is per thread since the tests run in parallel.
+struct CountingAlloc;
+
+thread_local! {
+    static ALLOCATIONS: Cell<usize> = const { Cell::new(0) };
+}
+
+unsafe impl GlobalAlloc for CountingAlloc {
+    unsafe fn alloc(&self, layout: Layout) -> *mut u8 {
+        let _ = ALLOCATIONS.try_with(|count| count.set(count.get() + 1));
+        System.alloc(layout)
+    }
+
+    unsafe fn dealloc(&self, ptr: *mut u8, layout: Layout) {
+        System.dealloc(ptr, layout)
+    }
+}
+
+#[global_allocator]
+static GLOBAL: CountingAlloc = CountingAlloc;
+
+fn count_allocations<T>(f: impl FnOnce() -> T) -> (T, usize) {
+    let before = ALLOCATIONS.with(Cell::get);
+    let result = f();
+    (result, ALLOCATIONS.with(Cell::get) - before)
+}
+
+#[test]
+fn parse_without_params() {
+    for s in ["application/json", " image/svg+xml ;", "*/*"] {
+        let (media_type, allocations) = count_allocations(|| MediaType::parse(s).unwrap());
+        assert_eq!(allocations, 0, "{s:?}");
+
+        let (cloned, allocations) = count_allocations(|| media_type.clone());
+        assert_eq!(allocations, 0, "{s:?}");
+        assert_eq!(cloned, media_type);
+    }
+
+    let mut scratch = Vec::new();
+    let (_, allocations) =
+        count_allocations(|| MediaType::parse_into("text/plain", &mut scratch).is_ok());
+    assert_eq!(allocations, 0);
+}
+
+#[test]
+fn to_ref() {
+    let media_type: MediaTypeBuf = "image/svg+xml".parse().unwrap();
+    let (borrowed, allocations) = count_allocations(|| media_type.to_ref());
+    assert_eq!(allocations, 0);
+    assert_eq!(borrowed.to_string(), "image/svg+xml");
+
+    let media_type: MediaTypeBuf = "text/plain; charset=UTF-8; format=flowed".parse().unwrap();
+    let (borrowed, allocations) = count_allocations(|| MediaType::from(&media_type));
+    assert_eq!(allocations, 1);
+    let data = media_type.as_str().as_bytes().as_ptr_range();
+    assert!(data.contains(&borrowed.subty.as_str().as_ptr()));
+    for (name, value) in borrowed.params.iter() {
+        assert!(data.contains(&name.as_str().as_ptr()));
+        assert!(data.contains(&value.as_str().as_ptr()));
+    }
+}
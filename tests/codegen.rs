@@ -23,6 +23,8 @@ fn generated_code_is_fresh() {
             VALUES_HEADER,
         );
 
+    fresh = fresh && generate_registered("src/consts/iana.txt", "src/consts/iana.rs", IANA_HEADER);
+
     if !fresh {
         panic!("generated code is not fresh, please commit updates");
     }
@@ -40,7 +42,7 @@ fn generate_consts(
         .map(|&(pf, name, comment)| (pf, name, comment, String::with_capacity(1024)))
         .collect::<Vec<_>>();
 
-    let input = fs::read_to_string(&input).expect("failed to read input file");
+    let input = fs::read_to_string(input).expect("failed to read input file");
     for line in input.lines() {
         let (ident, name) = if let Some(pair) = line.split_once('=') {
             pair
@@ -85,6 +87,33 @@ fn generate_consts(
     old == new
 }
 
+fn generate_registered(input: &str, dst: &str, header: &str) -> bool {
+    let input = fs::read_to_string(input).expect("failed to read input file");
+    let mut types = input
+        .lines()
+        .map(|line| line.to_ascii_lowercase())
+        .collect::<Vec<_>>();
+    types.sort_unstable();
+    types.dedup();
+
+    let mut new = String::with_capacity(1024);
+    new.push_str(header);
+    new.push_str("\n\n");
+    writeln!(new, "/// Sorted `type/subtype` pairs in lowercase.").unwrap();
+    writeln!(new, "pub static REGISTERED: [&str; {}] = [", types.len()).unwrap();
+    for ty in types {
+        writeln!(new, "    \"{}\",", ty).unwrap();
+    }
+    new.push_str("];\n");
+
+    let old = fs::read_to_string(dst).expect("failed to read destination file");
+    if old != new {
+        fs::write(dst, &new).expect("failed to write destination file");
+    }
+
+    old == new
+}
+
 fn upper_snake_case(s: &str) -> String {
     let s = s
         .split_inclusive(char::is_uppercase)
@@ -124,3 +153,8 @@ const VALUES_HEADER: &str = r#"//! Predefined parameter values, @generated in te
 //! # Sources
 //! - <https://www.iana.org/assignments/character-sets/character-sets.xhtml>
 //! - <https://datatracker.ietf.org/doc/html/rfc3676>"#;
+
+const IANA_HEADER: &str = r#"//! Registered media types, @generated in tests/codegen.rs
+//!
+//! # Sources
+//! - <https://www.iana.org/assignments/media-types/media-types.xhtml>"#;
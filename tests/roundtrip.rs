@@ -0,0 +1,122 @@
+use mediatype::{MediaType, MediaTypeBuf, ReadParams};
+
+const FIRST_CHARS: &[u8] = b"abcdefghijklmnopqrstuvwxyzABCDEFGHIJKLMNOPQRSTUVWXYZ0123456789";
+const NAME_CHARS: &[u8] =
+    b"abcdefghijklmnopqrstuvwxyzABCDEFGHIJKLMNOPQRSTUVWXYZ0123456789!#$&-^_.%*'";
+const QUOTED_CHARS: &[u8] = b"abcXYZ019 \t!#$%&'()*+,-./:;<=>?@[]^_`{|}~";
+const OWS: &[&str] = &["", "", " ", "  ", "\t", " \t "];
+
+/// A small xorshift generator, so that failures are reproducible without extra dependencies.
+struct Rng(u64);
+
+impl Rng {
+    fn next(&mut self) -> u64 {
+        self.0 ^= self.0 << 13;
+        self.0 ^= self.0 >> 7;
+        self.0 ^= self.0 << 17;
+        self.0
+    }
+
+    fn below(&mut self, n: usize) -> usize {
+        (self.next() % n as u64) as usize
+    }
+
+    fn pick<T: Copy>(&mut self, items: &[T]) -> T {
+        items[self.below(items.len())]
+    }
+
+    fn name(&mut self, max_len: usize) -> String {
+        let len = self.below(max_len);
+        let mut s = String::new();
+        s.push(self.pick(FIRST_CHARS) as char);
+        for _ in 0..len {
+            s.push(self.pick(NAME_CHARS) as char);
+        }
+        s
+    }
+
+    fn value(&mut self) -> String {
+        match self.below(4) {
+            0 => self.name(8),
+            1 => (0..self.below(4))
+                .map(|_| self.pick(NAME_CHARS) as char)
+                .collect(),
+            _ => {
+                let mut s = String::from("\"");
+                for _ in 0..self.below(10) {
+                    match self.below(8) {
+                        0 => s.push_str("\\\""),
+                        1 => s.push_str("\\\\"),
+                        2 => s.push('é'),
+                        _ => s.push(self.pick(QUOTED_CHARS) as char),
+                    }
+                }
+                s.push('"');
+                s
+            }
+        }
+    }
+
+    fn media_type(&mut self) -> String {
+        let mut s = format!("{}/{}", self.name(10), self.name(20));
+        for _ in 0..self.below(3) {
+            s.push('+');
+            s.push_str(&self.name(6));
+        }
+        for _ in 0..self.below(5) {
+            s.push_str(self.pick(OWS));
+            s.push(';');
+            s.push_str(self.pick(OWS));
+            s.push_str(&self.name(10));
+            s.push('=');
+            s.push_str(&self.value());
+        }
+        if self.below(4) == 0 {
+            s.push_str(self.pick(OWS));
+            s.push(';');
+            s.push_str(self.pick(OWS));
+        }
+        s
+    }
+}
+
+#[test]
+fn parse_display_roundtrip() {
+    let mut rng = Rng(0x2545_f491_4f6c_dd1d);
+    for _ in 0..10_000 {
+        let input = rng.media_type();
+        let parsed = MediaType::parse(&input).unwrap_or_else(|err| panic!("{input:?}: {err:?}"));
+        let displayed = parsed.to_string();
+        let reparsed =
+            MediaType::parse(&displayed).unwrap_or_else(|err| panic!("{displayed:?}: {err:?}"));
+
+        assert_eq!(parsed, reparsed, "{input:?} -> {displayed:?}");
+        assert_eq!(parsed.ty.as_str(), reparsed.ty.as_str(), "{input:?}");
+        assert_eq!(parsed.subty.as_str(), reparsed.subty.as_str(), "{input:?}");
+        assert_eq!(
+            parsed.suffix.map(|suffix| suffix.as_str().to_string()),
+            reparsed.suffix.map(|suffix| suffix.as_str().to_string()),
+            "{input:?}"
+        );
+        assert!(
+            parsed
+                .params()
+                .zip(reparsed.params())
+                .all(|(a, b)| a.0.as_str() == b.0.as_str() && a.1.as_str() == b.1.as_str()),
+            "{input:?}"
+        );
+        assert_eq!(parsed.params_len(), reparsed.params_len(), "{input:?}");
+        assert_eq!(reparsed.to_string(), displayed, "{input:?}");
+
+        let buf = MediaTypeBuf::from_string(input.clone())
+            .unwrap_or_else(|err| panic!("{input:?}: {err:?}"));
+        assert_eq!(
+            buf.as_str(),
+            input.trim_end_matches([' ', '\t', ';']),
+            "{input:?}"
+        );
+        assert_eq!(buf.to_string(), displayed, "{input:?}");
+        assert_eq!(buf, parsed, "{input:?}");
+        assert_eq!(buf.canonicalize(), parsed.to_canonical(), "{input:?}");
+    }
+}